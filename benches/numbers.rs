@@ -0,0 +1,65 @@
+//! Compares `utils::numbers` with the nom-based `Parsable` implementations on the same input.
+//! Run with `cargo bench --bench numbers`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::utils::numbers::{ints, numbers};
+use advent_of_code::utils::random::Rng;
+use advent_of_code::utils::{comma_list, lines_of, separated, Parsable};
+
+const LINES: usize = 10_000;
+const SAMPLES: usize = 50;
+
+fn generate(rng: &mut Rng) -> String {
+    (0..LINES)
+        .map(|_| {
+            let values: Vec<String> = (0..4).map(|_| (rng.range(0..2_000_000) as i64 - 1_000_000).to_string()).collect();
+            format!("{}\n", values.join(", "))
+        })
+        .collect()
+}
+
+fn median<T>(f: impl Fn() -> T) -> Duration {
+    let mut samples: Vec<Duration> = (0..SAMPLES)
+        .map(|_| {
+            let timer = Instant::now();
+            black_box(f());
+            timer.elapsed()
+        })
+        .collect();
+
+    samples.sort_unstable();
+    samples[SAMPLES / 2]
+}
+
+fn compare(name: &str, nom: Duration, fast: Duration) {
+    println!(
+        "{name:<16} nom: {nom:>10.1?}   numbers: {fast:>10.1?}   {:.1}x",
+        nom.as_secs_f64() / fast.as_secs_f64()
+    );
+
+    assert!(fast < nom, "{name}: `numbers` is not faster than nom");
+}
+
+fn main() {
+    let input = generate(&mut Rng::new(2024));
+
+    let all_nom = median(|| {
+        let (_, lines) = lines_of(comma_list(i64::parse))(black_box(&input)).unwrap();
+        lines.iter().flatten().sum::<i64>()
+    });
+    let all_fast = median(|| numbers::<i64>(black_box(&input)).sum::<i64>());
+    compare("all integers", all_nom, all_fast);
+
+    let arrays_nom = median(|| {
+        let (_, lines) = lines_of(separated::<(i64, i64, i64, i64)>(", "))(black_box(&input)).unwrap();
+        lines.iter().map(|&(a, b, c, d)| a + b + c + d).sum::<i64>()
+    });
+    let arrays_fast = median(|| {
+        black_box(&input)
+            .lines()
+            .map(|line| ints::<4>(line).unwrap().iter().sum::<i64>())
+            .sum::<i64>()
+    });
+    compare("arrays per line", arrays_nom, arrays_fast);
+}
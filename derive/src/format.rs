@@ -0,0 +1,157 @@
+/// A piece of a `#[parsable("...")]` format. The pieces are parsed in sequence.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment {
+    Literal(String),
+    /// One or more spaces or tabs.
+    Space,
+    /// Any line ending.
+    Newline,
+    /// A placeholder, with an optional parser expression that replaces the field's `Parsable` implementation.
+    Field {
+        name: String,
+        parser: Option<String>,
+    },
+}
+
+/// Splits a format string into its [`Segment`]s.
+pub fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    let flush = |literal: &mut String, segments: &mut Vec<Segment>| {
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(std::mem::take(literal)));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("unmatched `}` in format, use `}}` to match a literal brace".into()),
+            '{' => {
+                let mut placeholder = String::new();
+                let mut depth = 0;
+
+                loop {
+                    match chars.next() {
+                        None => return Err("unclosed `{` in format".into()),
+                        Some('}') if depth == 0 => break,
+                        Some(c) => {
+                            depth += match c {
+                                '{' | '(' | '[' => 1,
+                                '}' | ')' | ']' => -1,
+                                _ => 0,
+                            };
+                            placeholder.push(c);
+                        }
+                    }
+                }
+
+                let (name, parser) = match placeholder.split_once(':') {
+                    Some((name, parser)) => (name.trim(), Some(parser.trim().to_string())),
+                    None => (placeholder.trim(), None),
+                };
+
+                if name.is_empty() {
+                    return Err("placeholders need a field name, e.g. `{x}` or `{0}`".into());
+                }
+
+                flush(&mut literal, &mut segments);
+                segments.push(Segment::Field {
+                    name: name.to_string(),
+                    parser,
+                });
+            }
+            ' ' | '\t' => {
+                while matches!(chars.peek(), Some(' ' | '\t')) {
+                    chars.next();
+                }
+
+                flush(&mut literal, &mut segments);
+                segments.push(Segment::Space);
+            }
+            '\n' => {
+                flush(&mut literal, &mut segments);
+                segments.push(Segment::Newline);
+            }
+            c => literal.push(c),
+        }
+    }
+
+    flush(&mut literal, &mut segments);
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_format, Segment::*};
+
+    fn field(name: &str, parser: Option<&str>) -> super::Segment {
+        Field {
+            name: name.into(),
+            parser: parser.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn splits_literals_and_fields() {
+        assert_eq!(
+            parse_format("p={position} v={velocity}"),
+            Ok(vec![
+                Literal("p=".into()),
+                field("position", None),
+                Space,
+                Literal("v=".into()),
+                field("velocity", None),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_parser_expressions() {
+        assert_eq!(
+            parse_format("Prize: {prize:parse_location('=')}\n"),
+            Ok(vec![
+                Literal("Prize:".into()),
+                Space,
+                field("prize", Some("parse_location('=')")),
+                Newline,
+            ])
+        );
+
+        assert_eq!(
+            parse_format("{0:nom::multi::many1(|i| Tile::parse(i))}"),
+            Ok(vec![field(
+                "0",
+                Some("nom::multi::many1(|i| Tile::parse(i))")
+            )])
+        );
+    }
+
+    #[test]
+    fn escapes_braces() {
+        assert_eq!(
+            parse_format("{{{x}}}"),
+            Ok(vec![
+                Literal("{".into()),
+                field("x", None),
+                Literal("}".into())
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        assert!(parse_format("{x").is_err());
+        assert!(parse_format("x}").is_err());
+        assert!(parse_format("{:u32}").is_err());
+    }
+}
@@ -0,0 +1,25 @@
+/// Derive macros for the `advent_of_code` crate. They are re-exported from `advent_of_code::utils`,
+/// next to the traits they implement.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod format;
+mod parsable;
+
+/// Derives `Parsable` from a format string, e.g. `#[parsable("p={position} v={velocity}")]`.
+///
+/// - `{field}` parses a field with its own `Parsable` implementation, `{0}` refers to a field of a tuple struct.
+/// - `{field:parser}` parses a field with any nom parser expression instead, e.g. `{prize:parse_location('=')}`.
+/// - Spaces match one or more spaces or tabs, `\n` matches any line ending. `{{` and `}}` match literal braces.
+/// - Everything else has to match literally.
+///
+/// Every variant of an enum needs its own format. A char, e.g. `#[parsable('#')]`, matches exactly that character,
+/// which is handy for tiles of a grid. Variants are tried in order.
+#[proc_macro_derive(Parsable, attributes(parsable))]
+pub fn derive_parsable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    parsable::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,201 @@
+/// Expands `#[derive(Parsable)]` into an implementation of `advent_of_code::utils::Parsable` built from nom parsers.
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, Lit, Member, Path, Result};
+
+use crate::format::{parse_format, Segment};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`Parsable` cannot be derived for generic types",
+        ));
+    }
+
+    let name = &input.ident;
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = format_attribute(&input.attrs, name.span())?;
+            expand_fields(&syn::parse_quote!(#name), &data.fields, &format)?
+        }
+        Data::Enum(data) => {
+            let parsers = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(i, variant)| {
+                    let format = format_attribute(&variant.attrs, variant.ident.span())?;
+                    let variant_name = &variant.ident;
+                    let body = expand_fields(
+                        &syn::parse_quote!(#name::#variant_name),
+                        &variant.fields,
+                        &format,
+                    )?;
+                    let function = format_ident!("parse_variant_{i}");
+
+                    Ok((function, body))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let (functions, bodies): (Vec<_>, Vec<_>) = parsers.into_iter().unzip();
+
+            quote! {
+                #(
+                    fn #functions(input: &str) -> ::nom::IResult<&str, #name> {
+                        #bodies
+                    }
+
+                    match #functions(input) {
+                        Err(::nom::Err::Error(_)) => {}
+                        result => return result,
+                    }
+                )*
+
+                Err(::nom::Err::Error(::nom::error::Error::new(input, ::nom::error::ErrorKind::Alt)))
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "`Parsable` cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl<'a> ::advent_of_code::utils::Parsable<'a> for #name {
+            fn parse(input: &'a str) -> ::nom::IResult<&'a str, Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// A format as declared by the attribute. Chars match exactly, strings are split into [`Segment`]s.
+enum Format {
+    Char(char),
+    Str(Vec<Segment>, Span),
+}
+
+fn format_attribute(attrs: &[Attribute], span: Span) -> Result<Format> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("parsable"))
+        .ok_or_else(|| Error::new(span, "missing format, e.g. `#[parsable(\"{x},{y}\")]`"))?;
+
+    match attr.parse_args::<Lit>()? {
+        Lit::Char(c) => Ok(Format::Char(c.value())),
+        Lit::Str(s) => {
+            let segments =
+                parse_format(&s.value()).map_err(|message| Error::new_spanned(&s, message))?;
+            Ok(Format::Str(segments, s.span()))
+        }
+        lit => Err(Error::new_spanned(
+            lit,
+            "expected a format string or a char",
+        )),
+    }
+}
+
+/// Generates statements that parse `format` and construct `constructor` from the parsed fields.
+fn expand_fields(constructor: &Path, fields: &Fields, format: &Format) -> Result<TokenStream> {
+    let (segments, span) = match format {
+        Format::Char(c) => (vec![Segment::Literal(c.to_string())], Span::call_site()),
+        Format::Str(segments, span) => (segments.clone(), *span),
+    };
+
+    let members: Vec<(Member, &syn::Type)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            (member, &field.ty)
+        })
+        .collect();
+
+    let mut parsed: Vec<Option<Ident>> = vec![None; members.len()];
+    let mut statements = vec![];
+
+    for segment in segments {
+        let statement = match segment {
+            Segment::Literal(literal) => quote! {
+                let (input, _) = ::nom::bytes::complete::tag(#literal)(input)?;
+            },
+            Segment::Space => quote! {
+                let (input, _) = ::nom::character::complete::space1(input)?;
+            },
+            Segment::Newline => quote! {
+                let (input, _) = ::nom::character::complete::line_ending(input)?;
+            },
+            Segment::Field { name, parser } => {
+                let index = members
+                    .iter()
+                    .position(|(member, _)| member_name(member) == name)
+                    .ok_or_else(|| Error::new(span, format!("no field named `{name}`")))?;
+
+                if parsed[index].is_some() {
+                    return Err(Error::new(
+                        span,
+                        format!("field `{name}` appears more than once"),
+                    ));
+                }
+
+                let binding = format_ident!("field_{}", member_name(&members[index].0));
+                let ty = members[index].1;
+                parsed[index] = Some(binding.clone());
+
+                match parser {
+                    Some(parser) => {
+                        let parser: Expr = syn::parse_str(&parser).map_err(|err| {
+                            Error::new(span, format!("invalid parser for `{name}`: {err}"))
+                        })?;
+
+                        quote! {
+                            let (input, #binding): (&str, #ty) = ::nom::Parser::parse(&mut (#parser), input)?;
+                        }
+                    }
+                    None => quote! {
+                        let (input, #binding) = <#ty as ::advent_of_code::utils::Parsable>::parse(input)?;
+                    },
+                }
+            }
+        };
+
+        statements.push(statement);
+    }
+
+    if let Some(index) = parsed.iter().position(Option::is_none) {
+        let name = member_name(&members[index].0);
+        return Err(Error::new(
+            span,
+            format!("field `{name}` is missing from the format"),
+        ));
+    }
+
+    let bindings = parsed.into_iter().flatten();
+    let construct = match fields {
+        Fields::Named(_) => {
+            let names = members.iter().map(|(member, _)| member);
+            quote!(#constructor { #( #names: #bindings ),* })
+        }
+        Fields::Unnamed(_) => quote!(#constructor( #( #bindings ),* )),
+        Fields::Unit => quote!(#constructor),
+    };
+
+    Ok(quote! {
+        #( #statements )*
+        Ok((input, #construct))
+    })
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
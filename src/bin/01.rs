@@ -4,7 +4,9 @@ use nom::character::complete::space1;
 use nom::sequence::separated_pair;
 use nom::IResult;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, examples = [
+    "01" => (11, 31),
+]);
 
 pub fn parse(input: &str) -> IResult<&str, Vec<(i64, i64)>> {
     let parse_line = separated_pair(i64::parse, space1, i64::parse);
@@ -39,16 +41,4 @@ mod tests {
         let result = parse(&input);
         assert_eq!(result, Ok(("", vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)])));
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
 }
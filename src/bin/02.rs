@@ -5,7 +5,9 @@ use nom::character::complete::line_ending;
 use nom::multi::separated_list1;
 use nom::IResult;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, examples = [
+    "02" => (2, 4),
+]);
 
 pub fn parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
     let (input, reports) = separated_list1(line_ending, Vec::parse)(input)?;
@@ -125,16 +127,4 @@ mod tests {
         let report = vec![30, 32, 33, 35, 38, 42, 41];
         assert_eq!(is_valid_report_with_replacement(&report, true), true);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
 }
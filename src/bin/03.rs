@@ -8,7 +8,9 @@ use nom::sequence::{delimited, separated_pair, terminated};
 use nom::IResult;
 use Instruction::{Do, Dont, Mul};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, examples = [
+    "03" => (161, 48),
+]);
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Instruction {
@@ -80,16 +82,4 @@ mod tests {
         let result = parse(&input);
         assert_eq!(result, Ok(("", vec![Mul(2, 4), Dont, Mul(5, 5), Mul(11, 8), Do, Mul(8, 5)])));
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(48));
-    }
 }
@@ -1,30 +1,23 @@
-use advent_of_code::utils::{end_of_file, Parsable};
+use advent_of_code::utils::{comma_list, end_of_file, lines_of, sections2, Parsable};
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete::line_ending;
-use nom::combinator::map;
-use nom::multi::{many1, separated_list1};
-use nom::sequence::separated_pair;
+use nom::sequence::terminated;
 use nom::IResult;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::HashMap;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, examples = [
+    "05" => (143, 123),
+]);
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Parsable)]
+#[parsable("{lesser}|{greater}")]
 struct Rule {
     lesser: u32,
     greater: u32,
 }
 
-impl Parsable<'_> for Rule {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let pair = separated_pair(u32::parse, tag("|"), u32::parse);
-        map(pair, |(lesser, greater)| Rule { lesser, greater })(input)
-    }
-}
-
+#[derive(Debug, PartialEq)]
 struct Input {
     rules: Vec<Rule>,
     updates: Vec<Vec<u32>>,
@@ -32,13 +25,7 @@ struct Input {
 
 impl Parsable<'_> for Input {
     fn parse(input: &'_ str) -> IResult<&'_ str, Self> {
-        let (input, rules) = separated_list1(line_ending, Rule::parse)(input)?;
-        let (input, _) = many1(line_ending)(input)?;
-
-        let parse_update = separated_list1(tag(","), u32::parse);
-        let (input, updates) = separated_list1(line_ending, parse_update)(input)?;
-
-        let (input, _) = end_of_file(input)?;
+        let (input, (rules, updates)) = terminated(sections2(lines_of(Rule::parse), lines_of(comma_list(u32::parse))), end_of_file)(input)?;
 
         Ok((input, Input { rules, updates }))
     }
@@ -93,6 +80,66 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::utils::property::{assert_round_trip, Arbitrary, Printable};
+    use advent_of_code::utils::random::Rng;
+
+    impl Arbitrary for Rule {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let (lesser, greater) = Arbitrary::arbitrary(rng);
+            Rule { lesser, greater }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            (self.lesser, self.greater)
+                .shrink()
+                .into_iter()
+                .map(|(lesser, greater)| Rule { lesser, greater })
+                .collect()
+        }
+    }
+
+    impl Printable for Rule {
+        fn print(&self) -> String {
+            format!("{}|{}", self.lesser, self.greater)
+        }
+    }
+
+    impl Input {
+        fn to_parts(&self) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
+            let rules = self.rules.iter().map(|rule| (rule.lesser, rule.greater)).collect();
+            (rules, self.updates.clone())
+        }
+
+        fn from_parts((rules, updates): (Vec<(u32, u32)>, Vec<Vec<u32>>)) -> Self {
+            let rules = rules.into_iter().map(|(lesser, greater)| Rule { lesser, greater }).collect();
+            Input { rules, updates }
+        }
+    }
+
+    impl Arbitrary for Input {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Input::from_parts(Arbitrary::arbitrary(rng))
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            self.to_parts().shrink().into_iter().map(Input::from_parts).collect()
+        }
+    }
+
+    impl Printable for Input {
+        fn print(&self) -> String {
+            let rules = self.rules.iter().map(Rule::print).join("\n");
+            let updates = self.updates.iter().map(|update| update.iter().join(",")).join("\n");
+
+            format!("{rules}\n\n{updates}\n")
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip::<Rule>();
+        assert_round_trip::<Input>();
+    }
 
     #[test]
     fn test_parse() {
@@ -107,16 +154,4 @@ mod tests {
 
         assert_eq!(input.updates[0], vec![75, 47, 61, 53, 29]);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
 }
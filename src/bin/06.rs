@@ -1,6 +1,12 @@
+use advent_of_code::template::{ANSI_BLUE, ANSI_GRAY};
 use advent_of_code::utils::location::direction::{DOWN, LEFT, RIGHT, UP};
 use advent_of_code::utils::location::{direction, Access2d, Location};
+use advent_of_code::utils::random::Rng;
+use advent_of_code::utils::simulation::{Breakpoint, Simulation};
+use advent_of_code::utils::terminal::Screen;
+use advent_of_code::utils::viz::{Canvas, Palette, BLACK, GRAY, GREEN, RED};
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
+use image::ImageResult;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::combinator::value;
@@ -8,8 +14,11 @@ use nom::multi::many1;
 use num::Zero;
 use std::collections::{HashMap, HashSet};
 use std::ops::RangeInclusive;
+use std::path::Path;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, generator = generate, simulation = simulate, render = render, examples = [
+    "06" => (41, 6),
+]);
 
 #[derive(Debug, PartialEq, Clone)]
 enum Tile {
@@ -32,6 +41,7 @@ fn parse(input: &str) -> nom::IResult<&str, Vec<Vec<Tile>>> {
     parse_input_by_lines(many1(Tile::parse))(input)
 }
 
+#[derive(Clone)]
 struct Map {
     obstacles: HashSet<Location<i32>>,
     start: Location<i32>,
@@ -54,26 +64,95 @@ impl Map {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (_, input) = parse(input).unwrap();
+/// The guard walking through the map, one tile per step, until they leave it.
+#[derive(Clone)]
+struct Patrol {
+    map: Map,
+    guard: Location<i32>,
+    direction: Location<i32>,
+    path: Vec<Location<i32>>,
+    turned: bool,
+}
 
-    let map = Map::new(&input);
+impl Patrol {
+    fn new(map: Map) -> Self {
+        Self {
+            guard: map.start,
+            direction: direction::UP,
+            path: vec![map.start],
+            turned: false,
+            map,
+        }
+    }
+}
 
-    let mut visited = HashSet::new();
-    let mut current = map.start;
-    let mut direction = direction::UP;
+impl Simulation for Patrol {
+    fn step(&mut self) -> bool {
+        if !self.map.range.contains(&self.guard) {
+            return false;
+        }
 
-    while map.range.contains(&current) {
-        visited.insert(current);
+        self.turned = false;
+        while self.map.obstacles.contains(&(self.guard + self.direction)) {
+            self.direction = self.direction.rotate_90_cw();
+            self.turned = true;
+        }
 
-        while map.obstacles.contains(&(current + direction)) {
-            direction = direction.rotate_90_cw();
+        self.guard = self.guard + self.direction;
+        if self.map.range.contains(&self.guard) {
+            self.path.push(self.guard);
         }
 
-        current = current + direction;
+        true
+    }
+
+    fn render(&self) -> Screen {
+        let mut screen = Screen::new();
+
+        for y in 0..self.map.size.y {
+            for x in 0..self.map.size.x {
+                screen.set(Location::new(x, y), '.', ANSI_GRAY);
+            }
+        }
+        screen.locations(&self.map.obstacles, '#', "");
+        screen.locations(&self.path, 'X', "");
+
+        if self.map.range.contains(&self.guard) {
+            let symbol = match self.direction {
+                UP => '^',
+                RIGHT => '>',
+                DOWN => 'v',
+                _ => '<',
+            };
+            screen.set(self.guard, symbol, ANSI_BLUE);
+        }
+
+        screen
     }
 
-    Some(visited.len())
+    fn inspect(&self) -> String {
+        format!(
+            "guard at {}, {} tiles visited",
+            self.guard,
+            self.path.iter().collect::<HashSet<_>>().len()
+        )
+    }
+
+    fn breakpoints() -> Vec<Breakpoint<Self>> {
+        vec![("turn", |patrol| patrol.turned)]
+    }
+}
+
+fn simulate(input: &str) -> Patrol {
+    let (_, tiles) = parse(input).unwrap();
+    Patrol::new(Map::new(&tiles))
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut patrol = simulate(input);
+    while patrol.step() {}
+
+    Some(patrol.path.into_iter().collect::<HashSet<_>>().len())
 }
 
 fn sim_obstacle_in_front(
@@ -178,6 +257,57 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(extra_obstacle_positions.len())
 }
 
+/// Renders the map with the path the guard walks to `path.png`.
+fn render(input: &str, output: &Path) -> ImageResult<()> {
+    let (_, tiles) = parse(input).unwrap();
+
+    let mut patrol = Patrol::new(Map::new(&tiles));
+    while patrol.step() {}
+
+    let palette = Palette::new(BLACK).with(Tile::Obstacle, GRAY).with(Tile::Guard, GREEN);
+    let mut canvas = Canvas::from_grid(&tiles, &palette, 6);
+    canvas.path(&patrol.path, RED);
+
+    canvas.save_png(output.join("path.png"))
+}
+
+/// Generates a `size`×`size` map with scattered obstacles. Obstacles that would trap the guard in a loop are removed.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut grid: Vec<Vec<u8>> = (0..size).map(|_| (0..size).map(|_| if rng.chance(0.08) { b'#' } else { b'.' }).collect()).collect();
+
+    let guard = Location::new(rng.index(size) as i32, rng.index(size) as i32);
+    grid.set_2d(guard, b'^');
+
+    while let Some(obstacle) = find_loop(&grid, guard) {
+        grid.set_2d(obstacle, b'.');
+    }
+
+    grid.into_iter().map(|row| String::from_utf8(row).unwrap()).collect::<Vec<_>>().join("\n")
+}
+
+/// Walks the guard and returns the last obstacle it turned at, if its path is a loop.
+fn find_loop(grid: &Vec<Vec<u8>>, start: Location<i32>) -> Option<Location<i32>> {
+    let mut visited = HashSet::new();
+    let mut current = start;
+    let mut direction = UP;
+    let mut last_obstacle = None;
+
+    while grid.get_2d(current).is_some() {
+        if !visited.insert((current, direction)) {
+            return last_obstacle;
+        }
+
+        while grid.get_2d(current + direction) == Some(&b'#') {
+            last_obstacle = Some(current + direction);
+            direction = direction.rotate_90_cw();
+        }
+
+        current = current + direction;
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,16 +356,4 @@ mod tests {
         assert_eq!(shortcut_map.get(&(Location::new(1, 1), RIGHT)), Some(&(Location::new(2, 1), DOWN)));
         assert_eq!(shortcut_map.get(&(Location::new(4, 1), RIGHT)), Some(&(Location::new(5, 1), DOWN)));
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
 }
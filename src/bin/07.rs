@@ -3,7 +3,12 @@ use nom::bytes::complete::tag;
 use nom::IResult;
 use std::collections::HashSet;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, variants = [
+    1: forwards => part_one_forwards,
+    2: forwards => part_two_forwards,
+], examples = [
+    "07" => (3749, 11387),
+]);
 
 #[derive(Debug, PartialEq)]
 struct Equation {
@@ -22,7 +27,6 @@ impl Parsable<'_> for Equation {
 }
 
 impl Equation {
-    #[allow(dead_code)]
     fn is_solvable(&self) -> bool {
         let mut possible_values = HashSet::from([self.numbers[0]]);
 
@@ -58,7 +62,6 @@ impl Equation {
         possible_values.contains(&0)
     }
 
-    #[allow(dead_code)]
     fn is_solvable_with_concat(&self) -> bool {
         let mut possible_values = HashSet::from([self.numbers[0]]);
 
@@ -129,6 +132,30 @@ pub fn part_two(input: &str) -> Option<u64> {
     )
 }
 
+pub fn part_one_forwards(input: &str) -> Option<u64> {
+    let (_, equations) = parse(input).unwrap();
+
+    Some(
+        equations
+            .into_iter()
+            .filter(|equation| equation.is_solvable())
+            .map(|equation| equation.result)
+            .sum(),
+    )
+}
+
+pub fn part_two_forwards(input: &str) -> Option<u64> {
+    let (_, equations) = parse(input).unwrap();
+
+    Some(
+        equations
+            .into_iter()
+            .filter(|equation| equation.is_solvable_with_concat())
+            .map(|equation| equation.result)
+            .sum(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,16 +322,4 @@ mod tests {
             false
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
 }
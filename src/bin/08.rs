@@ -1,19 +1,12 @@
-use advent_of_code::utils::location::{Access2d, Location};
-use advent_of_code::utils::parse_input;
+use advent_of_code::utils::grid::ByteGrid;
+use advent_of_code::utils::location::Location;
 use itertools::Itertools;
-use nom::character::complete::{anychar, line_ending};
-use nom::combinator::map;
-use nom::multi::{many1, many_till};
-use nom::IResult;
 use num::Zero;
 use std::collections::HashMap;
 
-advent_of_code::solution!(8);
-
-fn parse(input: &str) -> IResult<&str, Vec<Vec<char>>> {
-    let parse_line = map(many_till(anychar, line_ending), |x| x.0);
-    parse_input(many1(parse_line))(input)
-}
+advent_of_code::solution!(8, examples = [
+    "08" => (14, 34),
+]);
 
 #[derive(Debug, PartialEq)]
 struct Map {
@@ -22,24 +15,24 @@ struct Map {
 }
 
 impl Map {
-    fn new(tiles: &Vec<Vec<char>>) -> Self {
+    fn new(tiles: &ByteGrid) -> Self {
         let antennas = tiles
-            .iter_2d_keys()
-            .map(|loc| (tiles.get_2d(loc).copied().unwrap(), loc))
-            .filter(|(c, _)| *c != '.')
+            .iter()
+            .filter(|&(_, b)| b != b'.')
+            .map(|(loc, b)| (b as char, loc))
             .into_group_map()
             .into_iter()
             .collect();
 
-        let size = Location::new(tiles[0].len() as i32, tiles.len() as i32);
-
-        Self { antennas, size }
+        Self {
+            antennas,
+            size: tiles.size(),
+        }
     }
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (_, input) = parse(input).unwrap();
-    let map = Map::new(&input);
+    let map = Map::new(&ByteGrid::new(input));
 
     let result = map
         .antennas
@@ -58,8 +51,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (_, input) = parse(input).unwrap();
-    let map = Map::new(&input);
+    let map = Map::new(&ByteGrid::new(input));
 
     let result = map
         .antennas
@@ -84,20 +76,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse() {
+    fn test_grid() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (rest, result) = parse(&input).unwrap();
+        let grid = ByteGrid::new(&input);
 
-        assert!(rest.is_empty());
-        assert_eq!(result[1][8], '0');
-        assert_eq!(result[5][6], 'A');
+        assert_eq!(grid.get(Location::new(8, 1)), Some(b'0'));
+        assert_eq!(grid.get(Location::new(6, 5)), Some(b'A'));
     }
 
     #[test]
     fn test_map_mew() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (_, result) = parse(&input).unwrap();
-        let map = Map::new(&result);
+        let map = Map::new(&ByteGrid::new(&input));
 
         assert_eq!(
             map,
@@ -113,16 +103,4 @@ mod tests {
             }
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
 }
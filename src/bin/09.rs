@@ -1,9 +1,12 @@
 use advent_of_code::utils::end_of_file;
+use advent_of_code::utils::random::Rng;
 use itertools::Itertools;
 use nom::bytes::complete::take_while;
 use nom::IResult;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, generator = generate, examples = [
+    "09" => (1928, 2858),
+]);
 
 fn parse(input: &str) -> IResult<&str, Vec<u32>> {
     let (input, digits) = take_while(|c| ('0'..='9').contains(&c))(input)?;
@@ -99,6 +102,19 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(checksum)
 }
 
+/// Generates a disk map of `size` files with random lengths and gaps.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .flat_map(|i| {
+            let file = rng.range(1..10);
+            let gap = (i + 1 < size).then(|| rng.range(0..10));
+            [Some(file), gap]
+        })
+        .flatten()
+        .map(|digit| digit.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,16 +136,4 @@ mod tests {
         assert_eq!(contribute_checksum(1, 2), 3);
         assert_eq!(contribute_checksum(2, 2), 5);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
 }
@@ -4,7 +4,9 @@ use nom::bytes::complete::take_while;
 use nom::IResult;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, examples = [
+    "10" => (36, 81),
+]);
 
 fn parse(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
     let (input, map) = parse_input_by_lines(take_while(|c| ('0'..='9').contains(&c)))(input)?;
@@ -125,16 +127,4 @@ mod tests {
         assert_eq!(result[0], vec![8, 9, 0, 1, 0, 1, 2, 3]);
         assert_eq!(result[1], vec![7, 8, 1, 2, 1, 8, 7, 4]);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
 }
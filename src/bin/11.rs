@@ -1,8 +1,11 @@
+use advent_of_code::utils::random::Rng;
 use advent_of_code::utils::{parse_input, Parsable};
 use nom::IResult;
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, variants = [1: memo => part_one_memo], generator = generate, examples = [
+    "11" => (55312, 65601038650482),
+]);
 
 fn parse(input: &str) -> IResult<&str, Vec<u64>> {
     parse_input(Vec::parse)(input)
@@ -63,6 +66,18 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(input.iter().map(|&stone| next_memo(stone, 75, memo)).sum())
 }
 
+pub fn part_one_memo(input: &str) -> Option<u64> {
+    let (_, input) = parse(input).unwrap();
+
+    let memo = &mut HashMap::new();
+    Some(input.iter().map(|&stone| next_memo(stone, 25, memo)).sum())
+}
+
+/// Generates `size` stones with engravings of up to seven digits.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size).map(|_| rng.range(0..10_000_000).to_string()).collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,16 +90,4 @@ mod tests {
         assert!(rest.is_empty());
         assert_eq!(result, vec![125, 17]);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
-    }
 }
@@ -1,25 +1,19 @@
+use advent_of_code::utils::grid::ByteGrid;
 use advent_of_code::utils::location::direction::{DOWN, LEFT, RIGHT, UP};
 use advent_of_code::utils::location::{Access2d, Location};
-use advent_of_code::utils::parse_input_by_lines;
-use nom::character::complete::alpha1;
-use nom::combinator::map;
-use nom::IResult;
 use std::collections::HashSet;
 use std::iter::successors;
 
-advent_of_code::solution!(12);
-
-fn parse(input: &str) -> IResult<&str, Vec<Vec<char>>> {
-    let line = map(alpha1, |line: &str| line.chars().collect());
-    parse_input_by_lines(line)(input)
-}
+advent_of_code::solution!(12, examples = [
+    "12" => (1930, 1206),
+]);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, map) = parse(input).unwrap();
+    let map = ByteGrid::new(input);
 
     let mut visited = HashSet::new();
 
-    fn scout(loc: Location<i32>, category: &char, map: &Vec<Vec<char>>, visited: &mut HashSet<Location<i32>>) -> (u32, u32) {
+    fn scout(loc: Location<i32>, category: &u8, map: &ByteGrid, visited: &mut HashSet<Location<i32>>) -> (u32, u32) {
         let current = map.get_2d(loc);
 
         if current != Some(category) {
@@ -55,11 +49,11 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, map) = parse(input).unwrap();
+    let map = ByteGrid::new(input);
 
     let mut visited = HashSet::new();
 
-    fn area(loc: Location<i32>, category: &char, map: &Vec<Vec<char>>, visited: &mut HashSet<Location<i32>>) -> u32 {
+    fn area(loc: Location<i32>, category: &u8, map: &ByteGrid, visited: &mut HashSet<Location<i32>>) -> u32 {
         if visited.contains(&loc) {
             0
         } else if map.get_2d(loc) != Some(category) {
@@ -71,7 +65,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    fn find_all_side_parts(location: Location<i32>, category: &char, map: &Vec<Vec<char>>) -> Vec<(Location<i32>, Location<i32>)> {
+    fn find_all_side_parts(location: Location<i32>, category: &u8, map: &ByteGrid) -> Vec<(Location<i32>, Location<i32>)> {
         let mut visited = HashSet::new();
         let mut queue = vec![location];
 
@@ -99,8 +93,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     fn find_next_corner(
         corner: (Location<i32>, Location<i32>),
-        category: &char,
-        map: &Vec<Vec<char>>,
+        category: &u8,
+        map: &ByteGrid,
         visited: &mut HashSet<(Location<i32>, Location<i32>)>,
     ) -> Option<(Location<i32>, Location<i32>)> {
         let (location, forward) = corner;
@@ -128,7 +122,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    fn corners(loc: Location<i32>, category: &char, map: &Vec<Vec<char>>) -> u32 {
+    fn corners(loc: Location<i32>, category: &u8, map: &ByteGrid) -> u32 {
         let side_parts = find_all_side_parts(loc, category, map);
 
         let mut visited_corners = HashSet::new();
@@ -166,27 +160,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse() {
+    fn test_grid() {
         let input = advent_of_code::template::read_file("examples", DAY);
-        let (rest, result) = parse(&input).unwrap();
-
-        assert!(rest.is_empty());
-        assert_eq!(result.len(), 10);
-        assert!(result.iter().all(|row| row.len() == 10));
-
-        assert_eq!(result[0], vec!['R', 'R', 'R', 'R', 'I', 'I', 'C', 'C', 'F', 'F']);
-        assert_eq!(result[2], vec!['V', 'V', 'R', 'R', 'R', 'C', 'C', 'F', 'F', 'F']);
-    }
+        let grid = ByteGrid::new(&input);
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1930));
-    }
+        assert_eq!((grid.width(), grid.height()), (10, 10));
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
+        assert_eq!(grid.row(0), Some(&b"RRRRIICCFF"[..]));
+        assert_eq!(grid.row(2), Some(&b"VVRRRCCFFF"[..]));
     }
 }
@@ -1,30 +1,23 @@
 use advent_of_code::utils::location::Location;
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, line_ending};
+use nom::character::complete::char;
 use nom::combinator::map;
-use nom::sequence::{delimited, pair, preceded, separated_pair};
+use nom::sequence::{pair, preceded, separated_pair};
 use nom::IResult;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, examples = [
+    "13" => (480, 875318608908),
+]);
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Parsable)]
+#[parsable("Button A: {button_a:parse_location('+')}\nButton B: {button_b:parse_location('+')}\nPrize: {prize:parse_location('=')}\n")]
 struct Machine {
     button_a: Location<i64>,
     button_b: Location<i64>,
     prize: Location<i64>,
 }
 
-impl Parsable<'_> for Machine {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, button_a) = delimited(tag("Button A: "), parse_location('+'), line_ending)(input)?;
-        let (input, button_b) = delimited(tag("Button B: "), parse_location('+'), line_ending)(input)?;
-        let (input, prize) = delimited(tag("Prize: "), parse_location('='), line_ending)(input)?;
-
-        Ok((input, Machine { button_a, button_b, prize }))
-    }
-}
-
 fn parse_location(sep: char) -> impl FnMut(&str) -> IResult<&str, Location<i64>> {
     move |input: &str| {
         let parse_x = preceded(pair(tag("X"), char(sep)), i64::parse);
@@ -75,8 +68,37 @@ pub fn part_two(input: &str) -> Option<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::utils::property::{assert_round_trip, Arbitrary, Printable};
+    use advent_of_code::utils::random::Rng;
     use nom::Parser;
 
+    impl Arbitrary for Machine {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let (button_a, button_b, prize) = Arbitrary::arbitrary(rng);
+            Machine { button_a, button_b, prize }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            (self.button_a, self.button_b, self.prize)
+                .shrink()
+                .into_iter()
+                .map(|(button_a, button_b, prize)| Machine { button_a, button_b, prize })
+                .collect()
+        }
+    }
+
+    impl Printable for Machine {
+        fn print(&self) -> String {
+            let Machine { button_a: a, button_b: b, prize: p } = self;
+            format!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n", a.x, a.y, b.x, b.y, p.x, p.y)
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip::<Machine>();
+    }
+
     #[test]
     fn test_parse_location() {
         assert_eq!(parse_location('=').parse("X=1, Y=2"), Ok(("", Location::new(1, 2))));
@@ -116,16 +138,4 @@ mod tests {
             }
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908));
-    }
 }
@@ -1,37 +1,26 @@
+use advent_of_code::template::ANSI_GREEN;
 use advent_of_code::utils::location::Location;
+use advent_of_code::utils::simulation::{Breakpoint, Simulation};
+use advent_of_code::utils::terminal::Screen;
+use advent_of_code::utils::viz::{Canvas, WHITE};
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
-use image::ImageBuffer;
+use image::ImageResult;
 use itertools::Itertools;
-use nom::bytes::complete::tag;
-use nom::character::complete::space1;
-use nom::combinator::map;
-use nom::sequence::{preceded, separated_pair};
+use log::debug;
 use nom::IResult;
 use num::traits::Euclid;
 use std::cmp::Ordering::Equal;
+use std::path::Path;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, simulation = simulate, render = render);
 
-fn parse_location(input: &str) -> IResult<&str, Location<i32>> {
-    map(separated_pair(i32::parse, tag(","), i32::parse), |(x, y)| Location::new(x, y))(input)
-}
-
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Parsable)]
+#[parsable("p={position} v={velocity}")]
 struct Robot {
     position: Location<i32>,
     velocity: Location<i32>,
 }
 
-impl Parsable<'_> for Robot {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, position) = preceded(tag("p="), parse_location)(input)?;
-        let (input, _) = space1(input)?;
-        let (input, velocity) = preceded(tag("v="), parse_location)(input)?;
-
-        Ok((input, Robot { position, velocity }))
-    }
-}
-
 impl Robot {
     fn simulate_pos(&self, time: i32, area_size: &Location<i32>) -> Location<i32> {
         (self.position + self.velocity * time).rem_euclid(area_size)
@@ -50,6 +39,85 @@ fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
     parse_input_by_lines(Robot::parse)(input)
 }
 
+/// All robots moving through the area, one second per step. Runs until the positions repeat.
+#[derive(Clone)]
+struct Bathroom {
+    robots: Vec<Robot>,
+    size: Location<i32>,
+    seconds: i32,
+    initial_variance: Location<i32>,
+}
+
+impl Bathroom {
+    fn new(robots: Vec<Robot>, size: Location<i32>) -> Self {
+        let mut bathroom = Self {
+            robots,
+            size,
+            seconds: 0,
+            initial_variance: Location::new(0, 0),
+        };
+        bathroom.initial_variance = bathroom.variance();
+        bathroom
+    }
+
+    /// Variance of the x and the y coordinates of the robots. Both drop when they draw the tree.
+    fn variance(&self) -> Location<i32> {
+        let len = self.robots.len().max(1) as i32;
+        let mean = self.robots.iter().fold(Location::new(0, 0), |sum, robot| sum + robot.position) / len;
+
+        self.robots
+            .iter()
+            .map(|robot| robot.position - mean)
+            .fold(Location::new(0, 0), |sum, delta| sum + Location::new(delta.x.pow(2), delta.y.pow(2)))
+            / len
+    }
+}
+
+impl Simulation for Bathroom {
+    fn step(&mut self) -> bool {
+        if self.seconds == self.size.x * self.size.y {
+            return false;
+        }
+
+        self.robots.iter_mut().for_each(|robot| robot.step(&self.size));
+        self.seconds += 1;
+
+        true
+    }
+
+    fn render(&self) -> Screen {
+        let positions: Vec<_> = self.robots.iter().map(|robot| robot.position).collect();
+
+        // Mark the corners, so that the screen always shows the whole area.
+        let mut screen = Screen::new();
+        screen.set(Location::new(0, 0), '+', "");
+        screen.set(self.size - Location::new(1, 1), '+', "");
+        screen.locations(&positions, '#', ANSI_GREEN);
+        screen
+    }
+
+    fn inspect(&self) -> String {
+        let variance = self.variance();
+        format!("after {} seconds, variance x {} y {}", self.seconds, variance.x, variance.y)
+    }
+
+    fn breakpoints() -> Vec<Breakpoint<Self>> {
+        vec![
+            ("100", |bathroom| bathroom.seconds == 100),
+            ("clustered", |bathroom| {
+                let variance = bathroom.variance();
+                variance.x * 2 < bathroom.initial_variance.x && variance.y * 2 < bathroom.initial_variance.y
+            }),
+        ]
+    }
+}
+
+fn simulate(input: &str) -> Bathroom {
+    let (_, robots) = parse(input).unwrap();
+
+    Bathroom::new(robots, Location::new(101, 103))
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     part_one_inner(input, Location::new(101, 103))
 }
@@ -73,23 +141,16 @@ pub fn part_two(input: &str) -> Option<usize> {
     part_two_inner(input, Location::new(101, 103))
 }
 
-#[allow(dead_code)]
-pub fn part_two_generate_images(input: &str, size: Location<i32>, max: i32) -> Option<u32> {
-    let (_, robots) = parse(input).unwrap();
+/// Renders the robots at the second they form the christmas tree to `tree.png`.
+fn render(input: &str, output: &Path) -> ImageResult<()> {
+    let size = Location::new(101, 103);
+    let (_, mut robots) = parse(input).unwrap();
 
-    let mut robots = robots;
-
-    for iter in 1..max {
-        robots.iter_mut().for_each(|robot| robot.step(&size));
-
-        let mut image = ImageBuffer::new(size.x as u32, size.y as u32);
-        for robot in robots.iter() {
-            image.put_pixel(robot.position.x as u32, robot.position.y as u32, image::Rgb([255u8, 255u8, 255u8]));
-        }
-        image.save(format!("data/outputs/day14/{:04}.png", iter)).unwrap();
-    }
+    let seconds = part_two_inner(input, size).unwrap_or(0);
+    robots.iter_mut().for_each(|robot| robot.step_by(seconds as i32, &size));
 
-    None
+    let positions = robots.iter().map(|robot| robot.position).collect::<Vec<_>>();
+    Canvas::from_locations(&positions, size, WHITE, 4).save_png(output.join("tree.png"))
 }
 
 pub fn part_two_inner(input: &str, size: Location<i32>) -> Option<usize> {
@@ -135,6 +196,7 @@ pub fn part_two_inner(input: &str, size: Location<i32>) -> Option<usize> {
     }
 
     let min_y = find_min(&variances_y);
+    debug!("x clusters after {min_x} steps, y clusters after {min_y} cycles of {} steps", size.x);
 
     Some(min_x + min_y * size.x as usize)
 }
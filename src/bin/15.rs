@@ -1,18 +1,29 @@
 use crate::Tile::{Box, Empty, Wall};
 use crate::Tile2::{BoxLeft, BoxRight};
+use advent_of_code::template::runner::is_visualizing;
+use advent_of_code::template::{ANSI_BLUE, ANSI_GRAY, ANSI_YELLOW};
 use advent_of_code::utils::location::direction::{DOWN, LEFT, RIGHT, UP, ZERO};
 use advent_of_code::utils::location::{Access2d, Location};
-use advent_of_code::utils::{end_of_file, Parsable};
+use advent_of_code::utils::simulation::{Breakpoint, Simulation};
+use advent_of_code::utils::terminal::{Glyphs, Player, Screen};
+use advent_of_code::utils::viz::{Animation, Canvas, Palette, BLACK, BLUE, GRAY, YELLOW};
+use advent_of_code::utils::{end_of_file, lines_of, sections2, Parsable};
+use image::ImageResult;
 use nom::branch::alt;
 use nom::character::complete::{char, line_ending};
 use nom::combinator::{opt, value};
-use nom::multi::{many1, separated_list1};
+use nom::multi::many1;
 use nom::sequence::preceded;
 use nom::IResult;
 use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
 use Tile::Robot;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, simulation = simulate, render = render, examples = [
+    "15-1" => (2028, _),
+    "15-2" => (10092, 9021),
+]);
 
 #[derive(Debug, PartialEq, Clone)]
 enum Tile {
@@ -76,9 +87,8 @@ fn parse_direction(input: &str) -> IResult<&str, Location<i32>> {
 
 impl Parsable<'_> for Input {
     fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, mut map) = separated_list1(line_ending, many1(Tile::parse))(input)?;
-        let (input, _) = many1(line_ending)(input)?;
-        let (input, moves) = many1(preceded(opt(line_ending), parse_direction))(input)?;
+        let parse_moves = many1(preceded(opt(line_ending), parse_direction));
+        let (input, (mut map, moves)) = sections2(lines_of(many1(Tile::parse)), parse_moves)(input)?;
 
         let robot = map.iter_2d_keys().find(|&loc| map.get_2d(loc) == Some(&Robot)).unwrap();
         map.set_2d(robot, Empty);
@@ -89,35 +99,131 @@ impl Parsable<'_> for Input {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let (_, input) = Input::parse(input).unwrap();
+/// The robot of part one working through its moves, one per step.
+#[derive(Clone)]
+struct Warehouse {
+    map: Vec<Vec<Tile>>,
+    robot: Location<i32>,
+    moves: Rc<[Location<i32>]>,
+    next_move: usize,
+    pushed: bool,
+}
 
-    let mut map = input.map;
-    let mut robot = input.robot;
+impl Warehouse {
+    fn gps_sum(&self) -> i32 {
+        self.map
+            .iter_2d_keys()
+            .filter(|&loc| self.map.get_2d(loc) == Some(&Box))
+            .map(|loc| loc.x + loc.y * 100)
+            .sum()
+    }
+}
 
-    for dir in input.moves {
-        let new_robot = robot + dir;
+impl Simulation for Warehouse {
+    fn step(&mut self) -> bool {
+        let Some(&dir) = self.moves.get(self.next_move) else {
+            return false;
+        };
+
+        self.next_move += 1;
+        self.pushed = false;
 
-        match map.get_2d(new_robot) {
-            Some(Empty) => robot = new_robot,
+        let new_robot = self.robot + dir;
+
+        match self.map.get_2d(new_robot) {
+            Some(Empty) => self.robot = new_robot,
             Some(Box) => {
-                let first_non_box = new_robot.iter_ray(dir).find(|loc| map.get_2d(*loc) != Some(&Box)).unwrap();
-                if let Some(Empty) = map.get_2d(first_non_box) {
-                    map.set_2d(first_non_box, Box);
-                    map.set_2d(new_robot, Empty);
-                    robot = new_robot;
+                let first_non_box = new_robot.iter_ray(dir).find(|loc| self.map.get_2d(*loc) != Some(&Box)).unwrap();
+                if let Some(Empty) = self.map.get_2d(first_non_box) {
+                    self.map.set_2d(first_non_box, Box);
+                    self.map.set_2d(new_robot, Empty);
+                    self.robot = new_robot;
+                    self.pushed = true;
                 }
             }
             _ => { /* no op */ }
         }
+
+        true
     }
 
-    Some(
-        map.iter_2d_keys()
-            .filter(|&loc| map.get_2d(loc) == Some(&Box))
-            .map(|loc| loc.x + loc.y * 100)
-            .sum(),
-    )
+    fn render(&self) -> Screen {
+        let glyphs = Glyphs::new('.').with(Wall, '#', ANSI_GRAY).with(Box, 'O', ANSI_YELLOW);
+
+        let mut screen = Screen::from_grid(&self.map, &glyphs);
+        screen.set(self.robot, '@', ANSI_BLUE);
+        screen
+    }
+
+    fn inspect(&self) -> String {
+        let next = match self.moves.get(self.next_move) {
+            Some(&UP) => "^",
+            Some(&RIGHT) => ">",
+            Some(&DOWN) => "v",
+            Some(_) => "<",
+            None => "none",
+        };
+
+        format!("move {} of {}, next {next}, GPS sum {}", self.next_move, self.moves.len(), self.gps_sum())
+    }
+
+    fn breakpoints() -> Vec<Breakpoint<Self>> {
+        vec![("push", |warehouse| warehouse.pushed)]
+    }
+}
+
+fn simulate(input: &str) -> Warehouse {
+    let (_, input) = Input::parse(input).unwrap();
+
+    Warehouse {
+        map: input.map,
+        robot: input.robot,
+        moves: input.moves.into(),
+        next_move: 0,
+        pushed: false,
+    }
+}
+
+/// Renders the robot of part one working through its moves to `warehouse.gif`, in about 100 frames.
+fn render(input: &str, output: &Path) -> ImageResult<()> {
+    let mut warehouse = simulate(input);
+    let frame_interval = (warehouse.moves.len() / 100).max(1);
+
+    let palette = Palette::new(BLACK).with(Wall, GRAY).with(Box, YELLOW);
+    let draw = |warehouse: &Warehouse| {
+        let mut canvas = Canvas::from_grid(&warehouse.map, &palette, 6);
+        canvas.fill(warehouse.robot, BLUE);
+        canvas
+    };
+
+    let mut animation = Animation::new(50);
+    animation.push(&draw(&warehouse));
+
+    while warehouse.step() {
+        if warehouse.next_move.is_multiple_of(frame_interval) || warehouse.next_move == warehouse.moves.len() {
+            animation.push(&draw(&warehouse));
+        }
+    }
+
+    animation.save_gif(output.join("warehouse.gif"))
+}
+
+pub fn part_one(input: &str) -> Option<i32> {
+    let mut warehouse = simulate(input);
+    let mut player = Player::new(20.0);
+    let visualizing = is_visualizing();
+
+    loop {
+        if visualizing {
+            player.show_with_caption(&warehouse.render(), &warehouse.inspect());
+        }
+
+        if !warehouse.step() {
+            break;
+        }
+    }
+
+    Some(warehouse.gps_sum())
 }
 
 pub fn part_two(_input: &str) -> Option<i32> {
@@ -207,22 +313,4 @@ mod tests {
         assert_eq!(input.moves.len(), 700);
         assert_eq!(input.moves[71], DOWN);
     }
-
-    #[test]
-    fn test_part_one_small() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some(2028));
-    }
-
-    #[test]
-    fn test_part_one_large() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(10092));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(9021));
-    }
 }
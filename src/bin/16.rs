@@ -1,38 +1,36 @@
-use crate::Tile::{Empty, End, Start, Wall};
+use crate::Tile::{End, Start, Wall};
 use advent_of_code::utils::location::direction::{DOWN, LEFT, RIGHT, UP};
 use advent_of_code::utils::location::Access2d;
+use advent_of_code::utils::location::Location;
+use advent_of_code::utils::random::{maze, Rng};
+use advent_of_code::utils::viz::{Canvas, Palette, BLACK, GRAY, GREEN, RED, YELLOW};
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 use hashbrown::{HashMap, HashSet};
+use image::ImageResult;
 use itertools::Itertools;
-use nom::branch::alt;
-use nom::character::complete::char;
-use nom::combinator::value;
 use nom::multi::many1;
 use nom::IResult;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::path::Path;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, generator = generate, render = render, examples = [
+    "16-1" => (7036, 45),
+    "16-2" => (11048, 64),
+]);
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Parsable)]
 enum Tile {
+    #[parsable('.')]
     Empty,
+    #[parsable('#')]
     Wall,
+    #[parsable('S')]
     Start,
+    #[parsable('E')]
     End,
 }
 
-impl Parsable<'_> for Tile {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        alt((
-            value(Empty, char('.')),
-            value(Wall, char('#')),
-            value(Start, char('S')),
-            value(End, char('E')),
-        ))(input)
-    }
-}
-
 fn parse(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
     parse_input_by_lines(many1(Tile::parse))(input)
 }
@@ -74,6 +72,11 @@ pub fn part_one(input: &str) -> Option<i32> {
 pub fn part_two(input: &str) -> Option<usize> {
     let (_, map) = parse(input).unwrap();
 
+    Some(best_path_tiles(&map).len())
+}
+
+/// Returns all tiles that are part of any of the best paths.
+fn best_path_tiles(map: &Vec<Vec<Tile>>) -> HashSet<Location<i32>> {
     let start = map.iter_2d_keys().find(|&loc| map.get_2d(loc) == Some(&Start)).unwrap();
     let end = map.iter_2d_keys().find(|&loc| map.get_2d(loc) == Some(&End)).unwrap();
 
@@ -121,12 +124,55 @@ pub fn part_two(input: &str) -> Option<usize> {
         }
     }
 
-    Some(best_paths.iter().unique_by(|&(loc, _)| loc).count())
+    best_paths.into_iter().map(|(loc, _)| loc).collect()
+}
+
+/// Renders the maze with all tiles of the best paths to `paths.png`.
+fn render(input: &str, output: &Path) -> ImageResult<()> {
+    let (_, map) = parse(input).unwrap();
+
+    let palette = Palette::new(BLACK).with(Wall, GRAY).with(Start, GREEN).with(End, RED);
+    let mut canvas = Canvas::from_grid(&map, &palette, 6);
+    canvas.locations(&best_path_tiles(&map), YELLOW);
+
+    canvas.save_png(output.join("paths.png"))
+}
+
+/// Generates a maze of `size`×`size` cells from the bottom left to the top right corner.
+/// Some walls are removed, so that there are several paths to choose from.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut walls = maze(size, size, rng);
+    let last = walls.len() - 1;
+
+    for (y, row) in walls.iter_mut().enumerate().take(last).skip(1) {
+        for (x, wall) in row.iter_mut().enumerate().take(last).skip(1) {
+            if (x + y) % 2 == 1 && rng.chance(0.1) {
+                *wall = false;
+            }
+        }
+    }
+
+    walls
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &wall)| match (x, y) {
+                    _ if wall => '#',
+                    (1, y) if y == last - 1 => 'S',
+                    (x, 1) if x == last - 1 => 'E',
+                    _ => '.',
+                })
+                .collect::<String>()
+        })
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Tile::Empty;
 
     #[test]
     fn test_parse() {
@@ -141,28 +187,4 @@ mod tests {
             vec![Wall, Empty, Wall, Empty, Wall, Wall, Wall, Empty, Wall, Empty, Wall, Wall, Wall, Empty, Wall]
         );
     }
-
-    #[test]
-    fn test_part_one_small() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some(7036));
-    }
-
-    #[test]
-    fn test_part_one_large() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(11048));
-    }
-
-    #[test]
-    fn test_part_two_small() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some(45));
-    }
-
-    #[test]
-    fn test_part_two_large() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, Some(64));
-    }
 }
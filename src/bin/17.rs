@@ -1,3 +1,7 @@
+use advent_of_code::template::{ANSI_GRAY, ANSI_YELLOW};
+use advent_of_code::utils::location::Location;
+use advent_of_code::utils::simulation::{Breakpoint, Simulation};
+use advent_of_code::utils::terminal::Screen;
 use advent_of_code::utils::{end_of_file, Parsable};
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
@@ -6,7 +10,10 @@ use nom::sequence::{delimited, preceded};
 use nom::IResult;
 use std::iter::successors;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, simulation = simulate, examples = [
+    "17-1" => ("4,6,3,5,6,3,5,2,1,0", _),
+    "17-2" => ("5,7,3,0", _),
+]);
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum ComboOperand {
@@ -134,6 +141,75 @@ impl Computer {
     }
 }
 
+/// The computer executing its program, one instruction per step, and the values it printed so far.
+#[derive(Debug, Clone)]
+struct Execution {
+    computer: Computer,
+    output: Vec<u64>,
+    printed: bool,
+}
+
+impl Simulation for Execution {
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.computer.read() else {
+            return false;
+        };
+
+        let printed = self.computer.execute_instruction(instruction);
+        self.output.extend(printed);
+        self.printed = printed.is_some();
+
+        true
+    }
+
+    fn render(&self) -> Screen {
+        let computer = &self.computer;
+        let mut screen = Screen::new();
+
+        for (y, (name, value)) in [("A", computer.reg_a), ("B", computer.reg_b), ("C", computer.reg_c)]
+            .into_iter()
+            .enumerate()
+        {
+            screen.text(Location::new(0, y as i32), &format!("{name} {value} ({value:o})"), "");
+        }
+
+        // Every value of the program is a single octal digit, so value `i` is in column `2 * i`.
+        let program = computer.memory.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        screen.text(Location::new(0, 4), &program, ANSI_GRAY);
+
+        let pointer = [computer.pointer, computer.pointer + 1].map(|i| Location::new(2 * i as i32, 4));
+        screen.highlight(pointer.iter().filter(|loc| (loc.x as usize) < program.len()), ANSI_YELLOW);
+
+        let output = self.output.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        screen.text(Location::new(0, 6), &format!("out {output}"), "");
+
+        screen
+    }
+
+    fn inspect(&self) -> String {
+        let memory = &self.computer.memory;
+
+        match memory.get(self.computer.pointer..self.computer.pointer + 2) {
+            Some(&[opcode, operand]) => format!("next {:?}", Instruction::from((opcode, operand))),
+            _ => "halted".to_string(),
+        }
+    }
+
+    fn breakpoints() -> Vec<Breakpoint<Self>> {
+        vec![("out", |execution| execution.printed)]
+    }
+}
+
+fn simulate(input: &str) -> Execution {
+    let (_, computer) = Computer::parse(input).unwrap();
+
+    Execution {
+        computer,
+        output: vec![],
+        printed: false,
+    }
+}
+
 pub fn part_one(input: &str) -> Option<String> {
     let (_, mut computer) = Computer::parse(input).unwrap();
 
@@ -174,6 +250,52 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::utils::property::{assert_round_trip, Arbitrary, Printable};
+    use advent_of_code::utils::random::Rng;
+
+    impl Arbitrary for Computer {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let ((reg_a, reg_b, reg_c), memory) = Arbitrary::arbitrary(rng);
+
+            Computer {
+                reg_a,
+                reg_b,
+                reg_c,
+                memory,
+                pointer: 0,
+            }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            ((self.reg_a, self.reg_b, self.reg_c), self.memory.clone())
+                .shrink()
+                .into_iter()
+                .map(|((reg_a, reg_b, reg_c), memory)| Computer {
+                    reg_a,
+                    reg_b,
+                    reg_c,
+                    memory,
+                    pointer: 0,
+                })
+                .collect()
+        }
+    }
+
+    impl Printable for Computer {
+        fn print(&self) -> String {
+            let program = self.memory.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+
+            format!(
+                "Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {program}\n",
+                self.reg_a, self.reg_b, self.reg_c
+            )
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip::<Computer>();
+    }
 
     #[test]
     fn test_parse_computer() {
@@ -196,9 +318,14 @@ mod tests {
     }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part("examples", DAY, 1));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".into()));
+    fn test_simulation() {
+        let input = &advent_of_code::template::read_file_part("examples", DAY, 1);
+        let mut execution = simulate(input);
+        while execution.step() {}
+
+        let output = execution.output.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        assert_eq!(Some(output), part_one(input));
+        assert_eq!(execution.inspect(), "halted");
     }
 
     #[test]
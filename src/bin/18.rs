@@ -1,9 +1,6 @@
 use advent_of_code::utils::location::Location;
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 use hashbrown::{HashMap, HashSet};
-use nom::character::complete::char;
-use nom::combinator::map;
-use nom::sequence::separated_pair;
 use nom::IResult;
 use num::Zero;
 use std::cmp::Reverse;
@@ -13,8 +10,7 @@ use std::hash::Hash;
 advent_of_code::solution!(18);
 
 fn parse(input: &str) -> IResult<&str, Vec<Location<i32>>> {
-    let parse_location = map(separated_pair(i32::parse, char(','), i32::parse), |(x, y)| Location::new(x, y));
-    parse_input_by_lines(parse_location)(input)
+    parse_input_by_lines(Location::parse)(input)
 }
 
 pub fn part_one(input: &str) -> Option<i32> {
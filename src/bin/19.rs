@@ -1,14 +1,18 @@
-use advent_of_code::utils::{end_of_file, Parsable};
+use advent_of_code::utils::{comma_list, end_of_file, lines_of, sections2, Parsable};
 use hashbrown::HashMap;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{char, line_ending, newline};
+use nom::character::complete::char;
 use nom::combinator::value;
-use nom::multi::{many1, separated_list1};
+use nom::multi::many1;
 use nom::IResult;
 use Color::{Black, Blue, Green, Red, White};
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, variants = [
+    1: naive => part_one_naive,
+    2: naive => part_two_naive,
+], examples = [
+    "19" => (6, 16),
+]);
 
 #[derive(Debug, PartialEq, Copy, Clone, Hash, Eq)]
 enum Color {
@@ -73,17 +77,13 @@ impl Graph {
 
 impl Parsable<'_> for Input {
     fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, towels) = separated_list1(tag(", "), many1(Color::parse))(input)?;
-        let (input, _) = many1(line_ending)(input)?;
-
-        let (input, patterns) = separated_list1(newline, many1(Color::parse))(input)?;
+        let (input, (towels, patterns)) = sections2(comma_list(many1(Color::parse)), lines_of(many1(Color::parse)))(input)?;
         let (input, _) = end_of_file(input)?;
 
         Ok((input, Input { towels, patterns }))
     }
 }
 
-#[allow(dead_code)]
 fn is_pattern_possible(towels: &[Vec<Color>], pattern: &[Color]) -> bool {
     let mut possible = vec![false; pattern.len() + 1];
     possible[0] = true;
@@ -132,13 +132,12 @@ fn is_pattern_possible_with_graph(towels: &Graph, pattern: &[Color]) -> bool {
     possible[possible.len() - 1]
 }
 
-#[allow(dead_code)]
 fn count_patterns(towels: &[Vec<Color>], pattern: &[Color]) -> u64 {
     let mut possible = vec![0; pattern.len() + 1];
     possible[0] = 1;
 
     for i in 0..possible.len() {
-        if !possible[i] == 0 {
+        if possible[i] == 0 {
             continue;
         }
 
@@ -201,6 +200,24 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(input.patterns.iter().map(|pattern| count_patterns_with_graph(&graph, pattern)).sum())
 }
 
+pub fn part_one_naive(input: &str) -> Option<usize> {
+    let (_, input) = Input::parse(input).unwrap();
+
+    Some(
+        input
+            .patterns
+            .iter()
+            .filter(|pattern| is_pattern_possible(&input.towels, pattern))
+            .count(),
+    )
+}
+
+pub fn part_two_naive(input: &str) -> Option<u64> {
+    let (_, input) = Input::parse(input).unwrap();
+
+    Some(input.patterns.iter().map(|pattern| count_patterns(&input.towels, pattern)).sum())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,16 +251,4 @@ mod tests {
             ],
         );
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(16));
-    }
 }
@@ -1,4 +1,5 @@
 use advent_of_code::utils::location::{Access2d, Location};
+use advent_of_code::utils::random::{maze, Rng};
 use advent_of_code::utils::{parse_input_by_lines, Parsable};
 use nom::branch::alt;
 use nom::character::complete::char;
@@ -8,7 +9,7 @@ use nom::IResult;
 use num::Zero;
 use std::collections::VecDeque;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, generator = generate);
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Tile {
@@ -144,6 +145,29 @@ pub fn part_two_inner(input: &str, at_least: u32) -> Option<usize> {
     )
 }
 
+/// Generates a maze of `size`×`size` cells with a single path from the top left to the bottom right corner.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let walls = maze(size, size, rng);
+    let last = walls.len() - 2;
+
+    walls
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &wall)| match (x, y) {
+                    _ if wall => '#',
+                    (1, 1) => 'S',
+                    (x, y) if x == last && y == last => 'E',
+                    _ => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,14 +1,14 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, report, scaffold, scale, solve, status, time, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
     use advent_of_code::template::Day;
-    use std::process;
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -26,18 +26,39 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            memory: bool,
             submit: Option<u8>,
+            variant: Option<String>,
+            watch: bool,
+            visualize: bool,
+            step: bool,
+            render: bool,
+            verbosity: u8,
         },
         All {
             release: bool,
+            timeout: Option<Duration>,
+        },
+        Status {
+            json: bool,
+        },
+        Report,
+        Scale {
+            day: Day,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            memory: bool,
+            variants: bool,
+            timeout: Option<Duration>,
+            against: Option<String>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -46,15 +67,31 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                timeout: parse_timeout(&mut args)?,
+            },
+            Some("status") => AppArguments::Status {
+                json: args.contains("--json"),
+            },
+            Some("report") => AppArguments::Report,
+            Some("scale") => AppArguments::Scale {
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let variants = args.contains("--variants");
+                let timeout = parse_timeout(&mut args)?;
+                let against = args.opt_value_from_str("--against")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    memory,
+                    variants,
+                    timeout,
+                    against,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -72,10 +109,23 @@ mod args {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                variant: args.opt_value_from_str("--variant")?,
                 dhat: args.contains("--dhat"),
+                memory: args.contains("--memory"),
+                watch: args.contains("--watch"),
+                visualize: args.contains("--visualize"),
+                step: args.contains("--step"),
+                render: args.contains("--render"),
+                verbosity: if args.contains("-vv") {
+                    2
+                } else {
+                    u8::from(args.contains("-v"))
+                },
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +143,16 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `--timeout <seconds>`.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_value_from_str::<_, f64>("--timeout")?
+            .map(Duration::try_from_secs_f64)
+            .transpose()?)
+    }
 }
 
 fn main() {
@@ -102,8 +162,19 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { release, timeout } => all::handle(release, timeout),
+            AppArguments::Status { json } => status::handle(json),
+            AppArguments::Report => report::handle(),
+            AppArguments::Scale { day } => scale::handle(day),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                memory,
+                variants,
+                timeout,
+                against,
+            } => time::handle(day, all, store, memory, variants, timeout, against),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -116,29 +187,41 @@ fn main() {
                     download::handle(day);
                 }
             }
+            AppArguments::Solve {
+                day,
+                release,
+                watch: true,
+                ..
+            } => watch::handle(day, release),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                memory,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
+                variant,
+                watch: false,
+                visualize,
+                step,
+                render,
+                verbosity,
+            } => {
+                if dhat && memory {
+                    eprintln!("The `--dhat` and `--memory` flags cannot be combined.");
+                    std::process::exit(1);
+                }
+                let flags = solve::SolutionFlags {
+                    submit_part: submit,
+                    variant,
+                    visualize,
+                    step,
+                    render,
+                    verbosity,
                 };
+                solve::handle(day, release, dhat, memory, flags);
             }
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
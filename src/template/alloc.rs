@@ -0,0 +1,119 @@
+/// A global allocator that counts allocations, so that the runner can report the memory usage of a part.
+/// It is installed by the `solution!` macro when the `count-allocations` feature is enabled.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::template::timings::Memory;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts every allocation.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` and returns the allocations it made.
+/// Returns `None` for the memory if the counting allocator is not installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    let (result, memory) = count(f);
+    (
+        result,
+        cfg!(feature = "count-allocations").then_some(memory),
+    )
+}
+
+fn count<T>(f: impl FnOnce() -> T) -> (T, Memory) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Relaxed);
+    let baseline = CURRENT_BYTES.load(Relaxed);
+    PEAK_BYTES.store(baseline, Relaxed);
+
+    let result = f();
+
+    let memory = Memory {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Relaxed) - allocated_bytes,
+        peak_bytes: PEAK_BYTES.load(Relaxed).saturating_sub(baseline),
+    };
+
+    (result, memory)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count, CountingAlloc};
+    use crate::template::timings::Memory;
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let large = Layout::from_size_align(256, 8).unwrap();
+
+        let ((), memory) = count(|| unsafe {
+            let a = CountingAlloc.alloc(small);
+            let b = CountingAlloc.alloc(large);
+            CountingAlloc.dealloc(b, large);
+            let c = CountingAlloc.realloc(a, small, 128);
+            CountingAlloc.dealloc(c, Layout::from_size_align(128, 8).unwrap());
+        });
+
+        assert_eq!(
+            memory,
+            Memory {
+                allocations: 3,
+                allocated_bytes: 448,
+                peak_bytes: 320,
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{stderr, stdout, Write},
     process::{Command, Output, Stdio},
 };
 
@@ -77,7 +78,7 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_captured(&args)
 }
 
 fn get_input_path(day: Day) -> String {
@@ -88,7 +89,7 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -123,3 +124,21 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Same as `call_aoc_cli`, but captures the output so the caller can inspect it.
+/// The captured output is still forwarded to stdout / stderr.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let _ = stdout().write_all(&output.stdout);
+    let _ = stderr().write_all(&output.stderr);
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
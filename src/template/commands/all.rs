@@ -1,5 +1,16 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::{run_multi, RunOptions},
+};
+
+pub fn handle(is_release: bool, timeout: Option<Duration>) {
+    let options = RunOptions {
+        is_release,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    run_multi(&all_days().collect(), options);
 }
@@ -1,6 +1,12 @@
 pub mod all;
 pub mod download;
 pub mod read;
+pub mod report;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod watch;
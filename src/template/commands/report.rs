@@ -0,0 +1,26 @@
+use std::{fs, process};
+
+use crate::template::history::History;
+use crate::template::report::{render_html, render_png, HTML_PATH, PNG_PATH};
+use crate::template::timings::Timings;
+
+pub fn handle() {
+    let timings = Timings::read_from_file();
+
+    if timings.data.is_empty() {
+        eprintln!("No stored timings found. Run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let history = History::read_from_file();
+
+    match fs::write(HTML_PATH, render_html(&timings, &history)) {
+        Ok(()) => println!("Wrote report to \"{HTML_PATH}\"."),
+        Err(e) => eprintln!("Failed to write report: {e}"),
+    }
+
+    match render_png(&timings).save(PNG_PATH) {
+        Ok(()) => println!("Wrote chart to \"{PNG_PATH}\"."),
+        Err(e) => eprintln!("Failed to write chart: {e}"),
+    }
+}
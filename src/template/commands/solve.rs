@@ -2,7 +2,18 @@ use std::process::{Command, Stdio};
 
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// Flags that are passed on to the solution itself.
+#[derive(Debug, Default)]
+pub struct SolutionFlags {
+    pub submit_part: Option<u8>,
+    pub variant: Option<String>,
+    pub visualize: bool,
+    pub step: bool,
+    pub render: bool,
+    pub verbosity: u8,
+}
+
+pub fn handle(day: Day, release: bool, dhat: bool, memory: bool, flags: SolutionFlags) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,13 +27,40 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if memory {
+        cmd_args.extend(["--features".to_string(), "count-allocations".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = flags.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(variant) = flags.variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant);
+    }
+
+    if flags.visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if flags.step {
+        cmd_args.push("--step".to_string());
+    }
+
+    if flags.render {
+        cmd_args.push("--render".to_string());
+    }
+
+    match flags.verbosity {
+        0 => {}
+        1 => cmd_args.push("-v".to_string()),
+        _ => cmd_args.push("-vv".to_string()),
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
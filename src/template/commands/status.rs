@@ -0,0 +1,14 @@
+use crate::template::status::{collect, render_grid, to_json};
+
+pub fn handle(json: bool) {
+    let statuses = collect();
+
+    if json {
+        match to_json(&statuses).stringify() {
+            Ok(json) => println!("{json}"),
+            Err(e) => eprintln!("Failed to serialize status: {e}"),
+        }
+    } else {
+        println!("{}", render_grid(&statuses));
+    }
+}
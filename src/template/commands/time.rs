@@ -1,10 +1,20 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::History;
+use crate::template::readme::{self, Context};
+use crate::template::run_multi::{run_multi, RunOptions};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, compare, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    memory: bool,
+    variants: bool,
+    timeout: Option<Duration>,
+    against: Option<String>,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,15 +31,46 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        count_allocations: memory,
+        variants,
+        timeout,
+    };
+
+    let timings = run_multi(&days_to_run, options).unwrap();
+
+    if let Some(rev) = against {
+        match compare::bench_revision(&rev, &days_to_run, options) {
+            Ok(baseline) => {
+                println!("\n{ANSI_BOLD}Comparison{ANSI_RESET}\n");
+                println!("{}", compare::render_comparison(&rev, &baseline, &timings));
+            }
+            Err(e) => {
+                eprintln!("Failed to bench {rev}: {e}");
+            }
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if !timings.data.is_empty() {
+            if let Err(e) = History::append_to_file(&timings) {
+                eprintln!("Failed to append to timings history: {e}");
+            }
+        }
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
+        let context = Context {
+            timings: merged_timings,
+            ..Context::read()
+        };
+
+        match readme::registered_sections().update_file(&context) {
+            Ok(_) => {
                 println!("Stored updated benchmarks.");
             }
             Err(_) => {
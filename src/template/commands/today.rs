@@ -0,0 +1,80 @@
+use std::{
+    io::{stdout, Write},
+    process,
+    time::Duration,
+};
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::unlock::{
+    format_countdown, next_unlock, puzzle_year, retry_with_backoff, wait_until, Clock,
+    SystemClock,
+};
+use crate::template::{aoc_cli, Day, ANSI_BOLD, ANSI_RESET};
+
+const FETCH_ATTEMPTS: u32 = 6;
+const FETCH_INITIAL_DELAY: Duration = Duration::from_secs(2);
+
+pub fn handle(wait: bool) {
+    if wait {
+        handle_wait(&SystemClock);
+        return;
+    }
+
+    match Day::today() {
+        Some(day) => {
+            scaffold::handle(day, false);
+            download::handle(day);
+            read::handle(day);
+        }
+        None => {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+            );
+            process::exit(1)
+        }
+    }
+}
+
+fn handle_wait(clock: &impl Clock) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let year = puzzle_year(clock.now(), aoc_cli::get_year());
+
+    let Some((day, unlock)) = next_unlock(clock.now(), year) else {
+        eprintln!(
+            "All puzzles of {year} have unlocked already. \
+            Please use `scaffold` with a specific day or update `AOC_YEAR` in `.cargo/config.toml`."
+        );
+        process::exit(1);
+    };
+
+    println!("Waiting for {ANSI_BOLD}day {day}{ANSI_RESET} to unlock at {unlock}.");
+
+    let mut stdout = stdout();
+    wait_until(clock, unlock, |remaining| {
+        print!("\r⏳ {}   ", format_countdown(remaining));
+        let _ = stdout.flush();
+    });
+    println!("\r🎄 Day {day} is unlocked!   ");
+
+    scaffold::handle(day, false);
+
+    // the puzzle might not be available the very second it unlocks, retry with increasing delays.
+    if let Err(e) = retry_with_backoff(clock, FETCH_ATTEMPTS, FETCH_INITIAL_DELAY, || {
+        aoc_cli::download(day)
+    }) {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = retry_with_backoff(clock, FETCH_ATTEMPTS, FETCH_INITIAL_DELAY, || {
+        aoc_cli::read(day)
+    }) {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,5 @@
+use crate::template::{watch::watch, Day};
+
+pub fn handle(day: Day, is_release: bool) {
+    watch(day, is_release);
+}
@@ -0,0 +1,274 @@
+/// Module that benches the solutions of another git revision and compares them to the working tree.
+/// The revision is checked out into a temporary worktree, but solutions run in the current directory so both sides read the same inputs.
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use crate::template::{
+    readme::markdown_table,
+    run_multi::{run_multi_in, Checkout, RunOptions},
+    timings::{PartTiming, Timings},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+static AGAINST_DIR: &str = "target/against";
+
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Git(message) => write!(f, "{message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A detached git worktree that is removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str, path: PathBuf) -> Result<Self, Error> {
+        git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{rev}^{{commit}}"),
+        ])
+        .map_err(|_| Error::Git(format!("`{rev}` is not a known revision.")))?;
+
+        // a previous run may have been interrupted before cleaning up.
+        if path.exists() {
+            let _ = git(&["worktree", "remove", "--force", &path.to_string_lossy()]);
+            let _ = fs::remove_dir_all(&path);
+        }
+
+        git(&["worktree", "add", "--detach", &path.to_string_lossy(), rev])?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]) {
+            eprintln!("Failed to remove worktree {}: {e}", self.path.display());
+        }
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git").args(args).output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Builds and benches `days` as of `rev`.
+pub fn bench_revision(
+    rev: &str,
+    days: &HashSet<Day>,
+    options: RunOptions,
+) -> Result<Timings, Error> {
+    println!("\n{ANSI_BOLD}Benching {rev}{ANSI_RESET}\n");
+
+    let against_dir = env::current_dir()?.join(AGAINST_DIR);
+    fs::create_dir_all(&against_dir)?;
+
+    let worktree = Worktree::add(rev, against_dir.join("worktree"))?;
+
+    // `Cargo.lock` is not checked in, reuse ours so both sides use the same dependency versions.
+    if Path::new("Cargo.lock").exists() {
+        fs::copy("Cargo.lock", worktree.path.join("Cargo.lock"))?;
+    }
+
+    let checkout = Checkout {
+        root: worktree.path.clone(),
+        target_dir: Some(against_dir.join("target")),
+    };
+
+    // allocations are not counted for the baseline: revisions that predate allocation counting
+    // lack the `count-allocations` feature and would fail to build.
+    let options = RunOptions {
+        count_allocations: false,
+        ..options
+    };
+
+    run_multi_in(&checkout, days, options)
+        .ok_or_else(|| Error::Git(format!("could not bench `{rev}`.")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the timings of both sides per part, with the speedup of the working tree over `rev`.
+pub fn render_comparison(rev: &str, baseline: &Timings, current: &Timings) -> String {
+    let find = |timings: &Timings, day: Day, part: u8| {
+        let timing = timings.data.iter().find(|t| t.day == day)?;
+        if part == 1 {
+            timing.part_1
+        } else {
+            timing.part_2
+        }
+    };
+
+    let mut days: Vec<Day> = baseline
+        .data
+        .iter()
+        .chain(&current.data)
+        .map(|t| t.day)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut rows: Vec<Vec<String>> = vec![];
+    // only parts measured on both sides are comparable.
+    let (mut total_before, mut total_after) = (0, 0);
+
+    for day in days {
+        for part in [1, 2] {
+            let (before, after) = (find(baseline, day, part), find(current, day, part));
+            if before.is_none() && after.is_none() {
+                continue;
+            }
+
+            if let (Some(before), Some(after)) = (before, after) {
+                total_before += before.nanos;
+                total_after += after.nanos;
+            }
+
+            rows.push(vec![
+                day.to_string(),
+                part.to_string(),
+                format_part(before),
+                format_part(after),
+                format_speedup(before.map(|p| p.nanos), after.map(|p| p.nanos)),
+            ]);
+        }
+    }
+
+    let before = PartTiming::from(Duration::from_nanos(total_before));
+    let after = PartTiming::from(Duration::from_nanos(total_after));
+
+    rows.push(vec![
+        "Total".into(),
+        String::new(),
+        before.format(),
+        after.format(),
+        format_speedup(Some(before.nanos), Some(after.nanos)),
+    ]);
+
+    markdown_table(&["Day", "Part", rev, "Working tree", "Speedup"], &rows).join("\n")
+}
+
+fn format_part(part: Option<PartTiming>) -> String {
+    part.map_or_else(|| "-".into(), |p| p.format())
+}
+
+fn format_speedup(before: Option<u64>, after: Option<u64>) -> String {
+    match (before, after) {
+        (Some(before), Some(after)) if after > 0 => {
+            #[allow(clippy::cast_precision_loss)]
+            let ratio = before as f64 / after as f64;
+            format!("{ratio:.2}x")
+        }
+        _ => "-".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_speedup, render_comparison};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn formats_speedup() {
+        assert_eq!(format_speedup(Some(300), Some(100)), "3.00x");
+        assert_eq!(format_speedup(Some(100), Some(400)), "0.25x");
+        assert_eq!(format_speedup(None, Some(100)), "-");
+        assert_eq!(format_speedup(Some(100), Some(0)), "-");
+    }
+
+    #[test]
+    fn renders_comparison() {
+        let baseline = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(Duration::from_millis(20).into()),
+                part_2: Some(Duration::from_millis(10).into()),
+                measured_at: None,
+                environment: None,
+            }],
+        };
+
+        let current = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(10).into()),
+                    measured_at: None,
+                    environment: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(Duration::from_millis(5).into()),
+                    part_2: None,
+                    measured_at: None,
+                    environment: None,
+                },
+            ],
+        };
+
+        let table = render_comparison("main", &baseline, &current);
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            "|  Day  | Part |  main  | Working tree | Speedup |"
+        );
+        assert_eq!(
+            lines[2],
+            "|  01   |  1   | 20.0ms |    10.0ms    |  2.00x  |"
+        );
+        assert_eq!(
+            lines[4],
+            "|  02   |  1   |   -    |    5.0ms     |    -    |"
+        );
+        assert_eq!(
+            lines[5],
+            "| Total |      | 30.0ms |    20.0ms    |  1.50x  |"
+        );
+    }
+}
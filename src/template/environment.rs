@@ -0,0 +1,333 @@
+/// Module that describes the machine and build that timings were measured with,
+/// so that numbers from different machines are not compared by accident.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::Path,
+    process::Command,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+/// The environment of a benchmark run. Fields that could not be determined are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    /// CPU model name, as reported by `/proc/cpuinfo`.
+    pub cpu: Option<String>,
+    pub cores: Option<u64>,
+    /// Version of `rustc`, e.g. `1.83.0`.
+    pub rustc: Option<String>,
+    /// Host target triple that the solutions were built for.
+    pub target: Option<String>,
+    /// Cargo profile that the solutions were built with.
+    pub profile: String,
+    pub opt_level: String,
+    /// Abbreviated hash of the checked out commit, suffixed with `-dirty` if there are uncommitted changes.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Environment {
+    /// Captures the environment of solutions that are built from `root`.
+    pub fn capture(root: &Path, is_release: bool) -> Self {
+        let profile = if is_release { "release" } else { "dev" };
+        let (rustc, target) = rustc_version();
+
+        Self {
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().ok().map(|n| n.get() as u64),
+            rustc,
+            target,
+            profile: profile.into(),
+            opt_level: opt_level(root, profile),
+            commit: commit(root),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether both environments describe the same machine and build, regardless of commit and time.
+    pub fn is_same_build(&self, other: &Self) -> bool {
+        let build = |environment: &Self| Self {
+            commit: None,
+            timestamp: 0,
+            ..environment.clone()
+        };
+
+        build(self) == build(other)
+    }
+
+    /// Describes the environment in a single line, e.g.
+    /// `AMD Ryzen 7 5800X (16 cores) · rustc 1.83.0 (x86_64-unknown-linux-gnu) · release, opt-level 3 · commit 1a2b3c4 · 2024-12-25`.
+    pub fn describe(&self) -> String {
+        [
+            Some(self.describe_build()),
+            self.commit
+                .as_ref()
+                .map(|commit| format!("commit {commit}")),
+            Some(format_date(self.timestamp)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+    }
+
+    /// Like [`Environment::describe`], without commit and date, which change on every run.
+    pub fn describe_build(&self) -> String {
+        let cores = self
+            .cores
+            .map(|cores| format!("{cores} {}", if cores == 1 { "core" } else { "cores" }));
+
+        let machine = match (&self.cpu, cores) {
+            (Some(cpu), Some(cores)) => Some(format!("{cpu} ({cores})")),
+            (Some(cpu), None) => Some(cpu.clone()),
+            (None, cores) => cores,
+        };
+
+        let rustc = match (&self.rustc, &self.target) {
+            (Some(rustc), Some(target)) => Some(format!("rustc {rustc} ({target})")),
+            (Some(rustc), None) => Some(format!("rustc {rustc}")),
+            (None, target) => target.clone(),
+        };
+
+        [
+            machine,
+            rustc,
+            Some(format!("{}, opt-level {}", self.profile, self.opt_level)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| matches!(key.trim(), "model name" | "Model" | "cpu model"))
+        .map(|(_, value)| value.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Reads version and host target from `rustc -vV`.
+fn rustc_version() -> (Option<String>, Option<String>) {
+    let Ok(output) = Command::new("rustc").arg("-vV").output() else {
+        return (None, None);
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = |key: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|value| value.trim().to_string())
+    };
+
+    (value("release:"), value("host:"))
+}
+
+/// Resolves the opt-level of `profile` like cargo does: env variable, then manifest, then cargo's default.
+fn opt_level(root: &Path, profile: &str) -> String {
+    let env_key = format!("CARGO_PROFILE_{}_OPT_LEVEL", profile.to_uppercase());
+
+    env::var(env_key)
+        .ok()
+        .or_else(|| {
+            let manifest = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+            manifest_opt_level(&manifest, profile)
+        })
+        .unwrap_or_else(|| if profile == "release" { "3" } else { "0" }.into())
+}
+
+fn manifest_opt_level(manifest: &str, profile: &str) -> Option<String> {
+    let header = format!("[profile.{profile}]");
+
+    manifest
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "opt-level")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
+
+fn commit(root: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let hash = git(&["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Formats a unix timestamp as an UTC date, e.g. `2024-12-25`.
+fn format_date(timestamp: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let string = |value: &Option<String>| match value {
+            Some(value) => JsonValue::String(value.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("cpu".into(), string(&value.cpu));
+        map.insert(
+            "cores".into(),
+            value
+                .cores
+                .map_or(JsonValue::Null, |cores| JsonValue::Number(cores as f64)),
+        );
+        map.insert("rustc".into(), string(&value.rustc));
+        map.insert("target".into(), string(&value.target));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "opt_level".into(),
+            JsonValue::String(value.opt_level.clone()),
+        );
+        map.insert("commit".into(), string(&value.commit));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .filter(|n| **n >= 0.0)
+                .map(|n| *n as u64)
+        };
+
+        Ok(Environment {
+            cpu: string("cpu"),
+            cores: number("cores"),
+            rustc: string("rustc"),
+            target: string("target"),
+            profile: string("profile").ok_or("Expected environment.profile to be a string.")?,
+            opt_level: string("opt_level")
+                .ok_or("Expected environment.opt_level to be a string.")?,
+            commit: string("commit"),
+            timestamp: number("timestamp")
+                .ok_or("Expected environment.timestamp to be a number.")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_date, manifest_opt_level, Environment};
+    use tinyjson::JsonValue;
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: Some("AMD Ryzen 7 5800X 8-Core Processor".into()),
+            cores: Some(16),
+            rustc: Some("1.83.0".into()),
+            target: Some("x86_64-unknown-linux-gnu".into()),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            commit: Some("1a2b3c4".into()),
+            timestamp: 1_735_084_800,
+        }
+    }
+
+    #[test]
+    fn describes_environment() {
+        assert_eq!(
+            get_mock_environment().describe(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 cores) · rustc 1.83.0 (x86_64-unknown-linux-gnu) · release, opt-level 3 · commit 1a2b3c4 · 2024-12-25"
+        );
+
+        let partial = Environment {
+            cpu: None,
+            rustc: None,
+            commit: None,
+            ..get_mock_environment()
+        };
+        assert_eq!(
+            partial.describe(),
+            "16 cores · x86_64-unknown-linux-gnu · release, opt-level 3 · 2024-12-25"
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_735_084_800 + 86_399), "2024-12-25");
+    }
+
+    #[test]
+    fn reads_opt_level_from_manifest() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[profile.dev]\nopt-level = 1\n\n[profile.release]\ndebug = 1\nopt-level = \"s\"\n";
+        assert_eq!(manifest_opt_level(manifest, "dev"), Some("1".into()));
+        assert_eq!(manifest_opt_level(manifest, "release"), Some("s".into()));
+        assert_eq!(manifest_opt_level("[package]\n", "release"), None);
+    }
+
+    #[test]
+    fn roundtrips_environment() {
+        let environment = get_mock_environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json), Ok(environment));
+    }
+}
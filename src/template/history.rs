@@ -0,0 +1,117 @@
+/// Module that keeps the timings of every stored benchmark run, so that reports can show how solutions evolved.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.json";
+
+/// Timings of past benchmark runs, oldest first.
+/// Every run only contains the days that were benched in it.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Timings>,
+}
+
+impl History {
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(HISTORY_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(History::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Appends a run to the history file.
+    pub fn append_to_file(timings: &Timings) -> Result<(), Error> {
+        let mut history = Self::read_from_file();
+        history.runs.push(timings.clone());
+
+        let json = JsonValue::from(&history);
+        let mut file = fs::File::create(HISTORY_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().cloned().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            runs: runs
+                .iter()
+                .map(Timings::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::History;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_history() {
+        let run = |millis| Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(Duration::from_millis(millis).into()),
+                part_2: None,
+                measured_at: None,
+                environment: None,
+            }],
+        };
+
+        let history = History {
+            runs: vec![run(20), run(10)],
+        };
+
+        let json = JsonValue::from(&history).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+
+        assert_eq!(parsed.runs.len(), 2);
+        assert_eq!(
+            parsed.runs[1].data[0].part_1,
+            history.runs[1].data[0].part_1
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_history() {
+        History::try_from(r#"{ "runs": {} }"#.to_string()).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+/// Module that prints the `log` macros of solutions to stderr, so that they do not mix with the answers on stdout.
+/// The verbosity is set with `-v` (debug) or `-vv` (trace), e.g. `cargo solve 15 -v`, or with the `AOC_LOG` environment variable.
+use std::env;
+use std::io::{stderr, Write};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::template::runner::is_benching;
+use crate::template::{ANSI_BLUE, ANSI_GRAY, ANSI_RED, ANSI_RESET, ANSI_YELLOW};
+
+/// Level that is logged without any flags.
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Warn;
+
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && !is_benching()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let color = match record.level() {
+            Level::Error => ANSI_RED,
+            Level::Warn => ANSI_YELLOW,
+            Level::Info => ANSI_BLUE,
+            Level::Debug | Level::Trace => ANSI_GRAY,
+        };
+
+        let _ = writeln!(stderr(), "{color}{:<5}{ANSI_RESET} {}", record.level(), record.args());
+    }
+
+    fn flush(&self) {
+        let _ = stderr().flush();
+    }
+}
+
+/// Installs the logger with the level given by the arguments of the process or `AOC_LOG`.
+/// Does nothing if a logger is installed already.
+pub fn init() {
+    let args: Vec<String> = env::args().collect();
+    let level = level(&args, env::var("AOC_LOG").ok().as_deref());
+
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// Determines the level from `-v`/`-vv` or, without these flags, from the value of `AOC_LOG`, e.g. `info`.
+fn level(args: &[String], env_level: Option<&str>) -> LevelFilter {
+    if args.iter().any(|arg| arg == "-vv") {
+        LevelFilter::Trace
+    } else if args.iter().any(|arg| arg == "-v") {
+        LevelFilter::Debug
+    } else {
+        env_level.and_then(|level| level.parse().ok()).unwrap_or(DEFAULT_LEVEL)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reads_level_from_flags() {
+        assert_eq!(level(&args(&["15"]), None), LevelFilter::Warn);
+        assert_eq!(level(&args(&["15", "-v"]), None), LevelFilter::Debug);
+        assert_eq!(level(&args(&["15", "-vv"]), None), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn reads_level_from_env() {
+        assert_eq!(level(&args(&["15"]), Some("info")), LevelFilter::Info);
+        assert_eq!(level(&args(&["15"]), Some("OFF")), LevelFilter::Off);
+        assert_eq!(level(&args(&["15"]), Some("loud")), LevelFilter::Warn);
+        assert_eq!(level(&args(&["15", "-v"]), Some("error")), LevelFilter::Debug);
+    }
+}
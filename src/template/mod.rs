@@ -1,19 +1,40 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod logger;
 pub mod runner;
 
 pub use day::*;
 
+pub(crate) mod compare;
 mod day;
+mod environment;
+mod history;
+mod readme;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+pub mod scale;
+mod status;
+mod submissions;
 mod timings;
+#[cfg(feature = "today")]
+mod unlock;
+mod watch;
+
+#[cfg(all(feature = "dhat-heap", feature = "count-allocations"))]
+compile_error!("The features `dhat-heap` and `count-allocations` both install a global allocator and cannot be combined.");
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+pub const ANSI_BLUE: &str = "\x1b[34m";
+pub const ANSI_GRAY: &str = "\x1b[90m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
@@ -36,9 +57,41 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Helper function that reads an example by file name, e.g. `15-1` for `data/examples/15-1.txt`.
+#[must_use]
+pub fn read_example(name: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd.join("data").join("examples").join(format!("{name}.txt"));
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open example file")
+}
+
+/// Helper function that returns the directory for files a solution writes, e.g. images of its state.
+/// Every day gets its own directory `data/outputs/{day}`, which is created if needed.
+#[must_use]
+pub fn output_dir(day: Day) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    let dir = cwd.join("data").join("outputs").join(day.to_string());
+    fs::create_dir_all(&dir).expect("could not create output directory");
+    dir
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Further options can be passed as `key = value` pairs:
+///  - `variants = [1: forwards => part_one_forwards]` registers alternative implementations of a part by name.
+///    They are selected with `--variant <name>`, `--variants` runs all of them.
+///    A test checks that all variants of a part agree on the example.
+///  - `generator = generate` registers a [`scale::Generator`] that `cargo scale` uses to bench growing inputs.
+///  - `simulation = simulate` registers a function that turns the input into a
+///    [`Simulation`](crate::utils::simulation::Simulation), which `cargo solve <day> --step` steps through.
+///  - `render = render` registers a [`runner::Render`] that `cargo solve <day> --render` calls to write images of the
+///    solution to [`output_dir`].
+///  - `examples = ["15-1" => (2028, _), "15-2" => (10092, 9021)]` generates tests that solve the named example files
+///    and compare the results to the expected answers. `_` skips a part that does not apply to an example, a part
+///    without any expected answer gets no test. `None` expects a part to not return an answer, as scaffolded days do.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +103,156 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, $( $key:ident = $value:tt ),+ $(,)?) => {
+        $crate::solution!(@options $day, [], [], [], [], [], $( $key = $value ),+);
+    };
+
+    (@options $day:expr, [$( $variants:tt )*], [$( $generator:tt )*], [$( $examples:tt )*], [$( $simulation:tt )*], [$( $render:tt )*], variants = [$( $value:tt )*] $(, $( $rest:tt )* )?) => {
+        $crate::solution!(@options $day, [$( $value )*], [$( $generator )*], [$( $examples )*], [$( $simulation )*], [$( $render )*], $( $( $rest )* )?);
+    };
+    (@options $day:expr, [$( $variants:tt )*], [$( $generator:tt )*], [$( $examples:tt )*], [$( $simulation:tt )*], [$( $render:tt )*], generator = $value:ident $(, $( $rest:tt )* )?) => {
+        $crate::solution!(@options $day, [$( $variants )*], [$value], [$( $examples )*], [$( $simulation )*], [$( $render )*], $( $( $rest )* )?);
+    };
+    (@options $day:expr, [$( $variants:tt )*], [$( $generator:tt )*], [$( $examples:tt )*], [$( $simulation:tt )*], [$( $render:tt )*], examples = [$( $value:tt )*] $(, $( $rest:tt )* )?) => {
+        $crate::solution!(@options $day, [$( $variants )*], [$( $generator )*], [$( $value )*], [$( $simulation )*], [$( $render )*], $( $( $rest )* )?);
+    };
+    (@options $day:expr, [$( $variants:tt )*], [$( $generator:tt )*], [$( $examples:tt )*], [$( $simulation:tt )*], [$( $render:tt )*], simulation = $value:ident $(, $( $rest:tt )* )?) => {
+        $crate::solution!(@options $day, [$( $variants )*], [$( $generator )*], [$( $examples )*], [$value], [$( $render )*], $( $( $rest )* )?);
+    };
+    (@options $day:expr, [$( $variants:tt )*], [$( $generator:tt )*], [$( $examples:tt )*], [$( $simulation:tt )*], [$( $render:tt )*], render = $value:ident $(, $( $rest:tt )* )?) => {
+        $crate::solution!(@options $day, [$( $variants )*], [$( $generator )*], [$( $examples )*], [$( $simulation )*], [$value], $( $( $rest )* )?);
+    };
+    (@options $day:expr, [$( $part:literal : $name:ident => $func:expr ),* $(,)?], [$( $generator:ident )?], [$( $examples:tt )*], [$( $simulation:ident )?], [$( $render:ident )?], ) => {
+        $crate::solution!(@setup $day);
+
+        fn solution_variants() -> Vec<$crate::template::runner::Variant> {
+            use $crate::template::runner::{Variant, DEFAULT_VARIANT};
+
+            vec![
+                Variant::new(DAY, 1, DEFAULT_VARIANT, part_one),
+                Variant::new(DAY, 2, DEFAULT_VARIANT, part_two),
+                $( Variant::new(DAY, $part, stringify!($name), $func), )*
+            ]
+        }
+
+        fn main() {
+            use $crate::template::scale::*;
+
+            $crate::template::logger::init();
+
+            if is_scaling() {
+                let generator: Option<Generator> = None $( .or(Some($generator)) )?;
+                run_scaling(DAY, generator, &solution_variants());
+                return;
+            }
+
+            let input = $crate::template::read_file("inputs", DAY);
+
+            if $crate::template::runner::is_stepping() {
+                $crate::solution!(@step input $( $simulation )?);
+            }
+
+            if $crate::template::runner::is_rendering() {
+                $crate::solution!(@render input $( $render )?);
+            }
+
+            $crate::template::runner::run_variants(&input, &solution_variants());
+        }
+
+        $crate::solution!(@variant_tests $( $name )*);
+        $crate::solution!(@example_tests $( $examples )*);
+    };
+
+    (@step $input:ident) => {
+        $crate::utils::simulation::missing_simulation(DAY);
+    };
+    (@step $input:ident $simulation:ident) => {
+        $crate::utils::simulation::step_through($simulation(&$input));
+        return;
+    };
+
+    (@render $input:ident) => {
+        $crate::template::runner::missing_render(DAY);
+    };
+    (@render $input:ident $render:ident) => {
+        $crate::template::runner::run_render(DAY, $render, &$input);
+        return;
+    };
+
+    (@example_tests) => {};
+    (@example_tests $( $example:literal => ($one:tt, $two:tt) ),+ $(,)?) => {
+        #[cfg(test)]
+        mod example_tests {
+            $crate::solution!(@example_test test_part_one, super::part_one, [] $( $example => $one, )+);
+            $crate::solution!(@example_test test_part_two, super::part_two, [] $( $example => $two, )+);
+        }
+    };
+
+    // collects the examples with an expected value for a part, a part without any does not get a test.
+    (@example_test $test:ident, $func:path, [$( $kept:tt )*] $example:literal => _, $( $rest:tt )*) => {
+        $crate::solution!(@example_test $test, $func, [$( $kept )*] $( $rest )*);
+    };
+    (@example_test $test:ident, $func:path, [$( $kept:tt )*] $example:literal => $expected:tt, $( $rest:tt )*) => {
+        $crate::solution!(@example_test $test, $func, [$( $kept )* ($example, $expected)] $( $rest )*);
+    };
+    (@example_test $test:ident, $func:path, []) => {};
+    (@example_test $test:ident, $func:path, [$( ($example:literal, $expected:tt) )+]) => {
+        #[test]
+        fn $test() {
+            $( $crate::solution!(@expect $func, $example, $expected); )+
+        }
+    };
+
+    (@expect $func:path, $example:literal, None) => {
+        let result = $func(&$crate::template::read_example($example));
+        assert_eq!(result, None, "example {}", $example);
+    };
+    (@expect $func:path, $example:literal, $expected:literal) => {
+        let result = $func(&$crate::template::read_example($example));
+        // compares the source of the literal, so that large numbers are not parsed as `i32`.
+        let expected = stringify!($expected).trim_matches('"');
+        assert_eq!(result.map(|x| x.to_string()).as_deref(), Some(expected), "example {}", $example);
+    };
+
+    (@variant_tests) => {};
+    (@variant_tests $( $name:ident )+) => {
+        #[cfg(test)]
+        mod variant_tests {
+            #[test]
+            fn test_variants_agree() {
+                let input = $crate::template::read_file("examples", super::DAY);
+                let result = $crate::template::runner::check_variants(&input, &super::solution_variants());
+                assert_eq!(result, Ok(()));
+            }
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+
+            $crate::template::logger::init();
+
+            if $crate::template::scale::is_scaling() {
+                $crate::template::scale::run_scaling(DAY, None, &[]);
+            }
+
+            if is_stepping() {
+                $crate::utils::simulation::missing_simulation(DAY);
+            }
+
+            if is_rendering() {
+                missing_render(DAY);
+            }
+
+            let input = $crate::template::read_file("inputs", DAY);
+            $( run_part($func, &input, DAY, $part); )*
+        }
+    };
+
+    (@setup $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +260,8 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        #[cfg(feature = "count-allocations")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
     };
 }
@@ -0,0 +1,463 @@
+/// Module that keeps marked sections of the readme up to date.
+/// A section is delimited by two occurrences of a marker comment like `<!--- benchmarking table --->`,
+/// a single marker is expanded into a section on first update.
+/// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::{cmp::Reverse, fs, io, time::Duration};
+
+use crate::template::{
+    all_days, aoc_cli, readme_benchmarks,
+    submissions::{Submissions, Verdict},
+    timings::Timings,
+};
+
+static README_PATH: &str = "README.md";
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Reads the readme file.
+pub fn read() -> Result<String, Error> {
+    Ok(String::from_utf8_lossy(&fs::read(README_PATH)?).to_string())
+}
+
+/// Returns the marker comment for a section name.
+pub fn marker(name: &str) -> String {
+    format!("<!--- {name} --->")
+}
+
+struct SectionPosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn locate_section(readme: &str, name: &str) -> Result<Option<SectionPosition>, Error> {
+    let marker = marker(name);
+    let matches: Vec<_> = readme.match_indices(&marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{name}: too many occurences of marker in README."
+        )));
+    }
+
+    Ok(matches
+        .first()
+        .zip(matches.last())
+        .map(|(first, last)| SectionPosition {
+            pos_start: first.0,
+            pos_end: last.0 + last.1.len(),
+        }))
+}
+
+/// Returns the content between the markers of a section, if the section exists.
+pub fn section_content<'a>(readme: &'a str, name: &str) -> Result<Option<&'a str>, Error> {
+    Ok(locate_section(readme, name)?.map(|p| &readme[p.pos_start..p.pos_end]))
+}
+
+/// Replaces the section `name` with `content`, keeping the markers around it.
+/// Errors if the marker does not occur in the readme.
+pub fn update_section(readme: &mut String, name: &str, content: &str) -> Result<(), Error> {
+    let position = locate_section(readme, name)?
+        .ok_or_else(|| Error::Parser(format!("{name}: could not find marker in README.")))?;
+
+    let marker = marker(name);
+    let section = format!("{marker}\n{content}\n{marker}");
+    readme.replace_range(position.pos_start..position.pos_end, &section);
+    Ok(())
+}
+
+/// Renders a markdown table with centered, column-aligned cells.
+/// Widths are counted in chars, so the output is stable as long as the cells do not change.
+pub fn markdown_table(headers: &[&str], rows: &[Vec<String>]) -> Vec<String> {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or_default()
+                + 2
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = width - cell.chars().count();
+                let left = padding / 2;
+                format!("{}{cell}{}", " ".repeat(left), " ".repeat(padding - left))
+            })
+            .collect();
+        format!("|{}|", cells.join("|"))
+    };
+
+    let separator: Vec<String> = widths
+        .iter()
+        .map(|width| format!(":{}:", "-".repeat(width - 2)))
+        .collect();
+
+    let mut lines = vec![
+        format_row(headers.to_vec()),
+        format!("|{}|", separator.join("|")),
+    ];
+
+    lines.extend(
+        rows.iter()
+            .map(|row| format_row(row.iter().map(String::as_str).collect())),
+    );
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Data available to section renderers.
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    pub timings: Timings,
+    pub submissions: Submissions,
+    pub year: Option<u16>,
+}
+
+impl Context {
+    pub fn read() -> Self {
+        Self {
+            timings: Timings::read_from_file(),
+            submissions: Submissions::read_from_file(),
+            year: aoc_cli::get_year(),
+        }
+    }
+}
+
+/// Renders the content of a section. Returning `None` leaves the section untouched.
+pub type Renderer = Box<dyn Fn(&Context) -> Option<String>>;
+
+/// A set of readme sections, each rendered by a function of the [`Context`].
+#[derive(Default)]
+pub struct Sections {
+    sections: Vec<(String, Renderer)>,
+}
+
+impl Sections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a renderer for the section marked with `<!--- {name} --->`.
+    #[must_use]
+    pub fn register(
+        mut self,
+        name: &str,
+        renderer: impl Fn(&Context) -> Option<String> + 'static,
+    ) -> Self {
+        self.sections.push((name.into(), Box::new(renderer)));
+        self
+    }
+
+    /// Updates every registered section that has a marker in `readme`.
+    /// Returns the names of the updated sections.
+    pub fn apply(&self, readme: &mut String, context: &Context) -> Result<Vec<String>, Error> {
+        let mut updated = vec![];
+
+        for (name, renderer) in &self.sections {
+            if locate_section(readme, name)?.is_none() {
+                continue;
+            }
+
+            if let Some(content) = renderer(context) {
+                update_section(readme, name, &content)?;
+                updated.push(name.clone());
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Updates the sections of the readme file on disk.
+    pub fn update_file(&self, context: &Context) -> Result<Vec<String>, Error> {
+        let mut readme = read()?;
+        let updated = self.apply(&mut readme, context)?;
+        fs::write(README_PATH, &readme)?;
+        Ok(updated)
+    }
+}
+
+/// The sections that are kept up to date by the template.
+/// Register additional sections here, they are only rendered if their marker is present in the readme.
+pub fn registered_sections() -> Sections {
+    Sections::new()
+        .register(
+            readme_benchmarks::SECTION,
+            |ctx| match readme_benchmarks::TableStyle::from_env() {
+                Ok(style) => Some(readme_benchmarks::construct_table(
+                    "##",
+                    &ctx.timings,
+                    &style,
+                )),
+                Err(e) => {
+                    eprintln!("Invalid benchmarking table style: {e}");
+                    None
+                }
+            },
+        )
+        .register(STARS_SECTION, construct_stars_table)
+        .register(LONGEST_RUNNING_SECTION, |ctx| {
+            construct_longest_running_table(ctx, 5)
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub static STARS_SECTION: &str = "advent_readme_stars table";
+pub static LONGEST_RUNNING_SECTION: &str = "longest-running days";
+
+/// Renders a table of earned stars from the recorded submissions.
+/// Mirrors the format of the `advent-readme-stars` Github action that writes the same section,
+/// and leaves the section to the action as long as no correct answer was recorded locally.
+fn construct_stars_table(context: &Context) -> Option<String> {
+    if !context
+        .submissions
+        .data
+        .iter()
+        .any(|s| s.verdict == Verdict::Correct)
+    {
+        return None;
+    }
+
+    let year = context.year.map(|y| y.to_string());
+
+    let mut lines: Vec<String> = vec![
+        format!("## {} Results", year.as_deref().unwrap_or("Advent of Code")),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+
+    for day in all_days() {
+        let part_1 = context.submissions.is_solved(day, 1);
+        let part_2 = context.submissions.is_solved(day, 2);
+
+        if !part_1 && !part_2 {
+            continue;
+        }
+
+        let label = match &year {
+            Some(year) => format!(
+                "[Day {}](https://adventofcode.com/{year}/day/{})",
+                day.into_inner(),
+                day.into_inner()
+            ),
+            None => format!("Day {}", day.into_inner()),
+        };
+
+        lines.push(format!("| {label} | {} | {} |", star(part_1), star(part_2)));
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// Renders the `count` days with the highest total run time.
+fn construct_longest_running_table(context: &Context, count: usize) -> Option<String> {
+    let mut timings = context.timings.data.clone();
+    timings.sort_by_key(|timing| Reverse(timing.total_nanos()));
+
+    let rows: Vec<Vec<String>> = timings
+        .iter()
+        .take(count)
+        .map(|timing| {
+            let total = Duration::from_nanos(timing.total_nanos());
+            vec![
+                format!(
+                    "[Day {}]({})",
+                    timing.day.into_inner(),
+                    readme_benchmarks::get_path_for_bin(timing.day)
+                ),
+                format!("`{total:.1?}`"),
+            ]
+        })
+        .collect();
+
+    let mut lines: Vec<String> = vec!["## Longest-running days".into(), String::new()];
+    lines.extend(markdown_table(&["Day", "Total"], &rows));
+
+    Some(lines.join("\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        markdown_table, marker, registered_sections, section_content, update_section, Context,
+        Sections,
+    };
+    use crate::{
+        day,
+        template::{
+            submissions::{Submission, Submissions, Verdict},
+            timings::{Timing, Timings},
+        },
+    };
+    use std::time::Duration;
+
+    fn get_mock_context() -> Context {
+        Context {
+            timings: Timings {
+                data: vec![
+                    Timing {
+                        day: day!(1),
+                        part_1: Some(Duration::from_millis(10).into()),
+                        part_2: Some(Duration::from_millis(20).into()),
+                        measured_at: None,
+                        environment: None,
+                    },
+                    Timing {
+                        day: day!(2),
+                        part_1: Some(Duration::from_millis(30).into()),
+                        part_2: Some(Duration::from_millis(40).into()),
+                        measured_at: None,
+                        environment: None,
+                    },
+                ],
+            },
+            submissions: Submissions {
+                data: vec![
+                    Submission {
+                        day: day!(1),
+                        part: 1,
+                        answer: "1".into(),
+                        verdict: Verdict::Correct,
+                    },
+                    Submission {
+                        day: day!(1),
+                        part: 2,
+                        answer: "2".into(),
+                        verdict: Verdict::Correct,
+                    },
+                    Submission {
+                        day: day!(2),
+                        part: 1,
+                        answer: "3".into(),
+                        verdict: Verdict::Correct,
+                    },
+                ],
+            },
+            year: Some(2024),
+        }
+    }
+
+    #[test]
+    fn expands_single_marker() {
+        let mut s = format!("foo\n{}\nbar", marker("test"));
+        update_section(&mut s, "test", "content").unwrap();
+        assert_eq!(s, "foo\n<!--- test --->\ncontent\n<!--- test --->\nbar");
+    }
+
+    #[test]
+    fn replaces_existing_section() {
+        let mut s = format!("foo\n{}\nold\n{}\nbar", marker("test"), marker("test"));
+        update_section(&mut s, "test", "new").unwrap();
+        update_section(&mut s, "test", "newer").unwrap();
+        assert_eq!(s, "foo\n<!--- test --->\nnewer\n<!--- test --->\nbar");
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_section(&mut s, "test", "content").unwrap();
+    }
+
+    #[test]
+    fn applies_registered_sections() {
+        let mut s = format!("{}\n\n{}\n{}", marker("a"), marker("c"), marker("d"));
+        let sections = Sections::new()
+            .register("a", |_| Some("first".into()))
+            .register("b", |_| Some("second".into()))
+            .register("c", |ctx| Some(ctx.timings.data.len().to_string()))
+            .register("d", |_| None);
+
+        let updated = sections.apply(&mut s, &get_mock_context()).unwrap();
+
+        assert_eq!(updated, vec!["a", "c"]);
+        assert_eq!(s.matches(&marker("d")).count(), 1);
+        assert_eq!(
+            section_content(&s, "a").unwrap().unwrap(),
+            "<!--- a --->\nfirst\n<!--- a --->"
+        );
+        assert_eq!(
+            section_content(&s, "c").unwrap().unwrap(),
+            "<!--- c --->\n2\n<!--- c --->"
+        );
+        assert_eq!(section_content(&s, "b").unwrap(), None);
+    }
+
+    #[test]
+    fn renders_stars_table() {
+        let mut s = marker("advent_readme_stars table");
+        registered_sections()
+            .apply(&mut s, &get_mock_context())
+            .unwrap();
+
+        let expected = [
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+        ]
+        .join("\n");
+
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn renders_longest_running_days() {
+        let mut s = marker("longest-running days");
+        registered_sections()
+            .apply(&mut s, &get_mock_context())
+            .unwrap();
+
+        let lines: Vec<_> = s.lines().collect();
+        assert_eq!(lines[5], "| [Day 2](./src/bin/02.rs) | `70.0ms` |");
+        assert_eq!(lines[6], "| [Day 1](./src/bin/01.rs) | `30.0ms` |");
+    }
+
+    #[test]
+    fn aligns_table_columns() {
+        let rows = vec![
+            vec!["a".to_string(), "`1.0µs`".to_string()],
+            vec!["bbbb".to_string(), "`10.0ms`".to_string()],
+        ];
+
+        assert_eq!(
+            markdown_table(&["Col", "Time"], &rows),
+            vec![
+                "| Col  |   Time   |",
+                "|:----:|:--------:|",
+                "|  a   | `1.0µs`  |",
+                "| bbbb | `10.0ms` |",
+            ]
+        );
+    }
+}
@@ -1,127 +1,307 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// Module that renders the benchmarking table of the readme.
+/// The table is column-aligned and only depends on the stored timings and the [`TableStyle`],
+/// so storing unchanged timings does not change the readme.
+use std::{env, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
+use crate::template::readme::{self, markdown_table, Error};
+use crate::template::timings::{format_bytes, Stats, Timing, Timings};
 use crate::template::Day;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+pub static SECTION: &str = "benchmarking table";
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{day}.rs")
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// A column of the benchmarking table, in addition to the day.
+///
+/// There is no column for the parse time: parts parse their input themselves, so it is included in their duration
+/// and not measured on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Part1,
+    Part2,
+    Total,
+    /// The median duration of each part, e.g. `9.8ms / 19.6ms`.
+    Median,
+    /// The number of benchmark samples of each part.
+    Samples,
+    /// The highest peak heap usage of both parts.
+    Memory,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Median => "Median",
+            Column::Samples => "Samples",
+            Column::Memory => "Peak memory",
+        }
+    }
+
+    fn cell(self, timing: &Timing) -> String {
+        let value = match self {
+            Column::Part1 => timing.part_1.map(|part| part.format()),
+            Column::Part2 => timing.part_2.map(|part| part.format()),
+            Column::Total => Some(format!(
+                "{:.1?}",
+                Duration::from_nanos(timing.total_nanos())
+            )),
+            Column::Median => per_part(timing, |stats| {
+                format!("{:.1?}", Duration::from_nanos(stats.median_nanos))
+            }),
+            Column::Samples => per_part(timing, |stats| stats.samples.to_string()),
+            Column::Memory => [timing.part_1, timing.part_2]
+                .iter()
+                .flatten()
+                .filter_map(|part| part.memory)
+                .map(|memory| memory.peak_bytes)
+                .max()
+                .map(format_bytes),
+        };
+
+        value.map_or_else(|| "-".into(), |v| format!("`{v}`"))
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Formats the stats of both parts as `part 1 / part 2`. Parts without stats, e.g. migrated from version 1, are `-`.
+fn per_part(timing: &Timing, format: impl Fn(&Stats) -> String) -> Option<String> {
+    let parts = [timing.part_1, timing.part_2].map(|part| part.and_then(|part| part.stats));
+
+    if parts.iter().all(Option::is_none) {
+        return None;
+    }
+
+    Some(
+        parts
+            .map(|stats| stats.as_ref().map_or_else(|| "-".into(), &format))
+            .join(" / "),
+    )
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+impl FromStr for Column {
+    type Err = String;
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "median" => Ok(Column::Median),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            _ => Err(format!(
+                "unknown column `{s}`, expected one of `part_1`, `part_2`, `total`, `median`, `samples`, `memory`."
+            )),
+        }
     }
+}
+
+/// The order of the rows of the benchmarking table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sort {
+    Day,
+    /// Slowest days first.
+    Duration,
+}
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+impl FromStr for Sort {
+    type Err = String;
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(Sort::Day),
+            "duration" => Ok(Sort::Duration),
+            _ => Err(format!(
+                "unknown sort order `{s}`, expected `day` or `duration`."
+            )),
+        }
+    }
+}
+
+/// Configures the benchmarking table.
+/// Read from the `AOC_BENCH_COLUMNS`, `AOC_BENCH_SORT` and `AOC_BENCH_HIGHLIGHT` env variables,
+/// which can be set in `.cargo/config.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableStyle {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Number of slowest days that are printed in bold.
+    pub highlight: usize,
+}
 
-    Ok(TablePosition { pos_start, pos_end })
+impl Default for TableStyle {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Part1, Column::Part2],
+            sort: Sort::Day,
+            highlight: 0,
+        }
+    }
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+impl TableStyle {
+    pub fn from_env() -> Result<Self, String> {
+        let default = Self::default();
+
+        let columns = match env::var("AOC_BENCH_COLUMNS") {
+            Ok(columns) if !columns.trim().is_empty() => columns
+                .split(',')
+                .map(Column::from_str)
+                .collect::<Result<_, _>>()?,
+            _ => default.columns,
+        };
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+        let sort = match env::var("AOC_BENCH_SORT") {
+            Ok(sort) if !sort.trim().is_empty() => sort.parse()?,
+            _ => default.sort,
+        };
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let highlight = match env::var("AOC_BENCH_HIGHLIGHT") {
+            Ok(highlight) if !highlight.trim().is_empty() => {
+                highlight.trim().parse().map_err(|_| {
+                    format!("expected AOC_BENCH_HIGHLIGHT to be a number, got `{highlight}`.")
+                })?
+            }
+            _ => default.highlight,
+        };
+
+        Ok(Self {
+            columns,
+            sort,
+            highlight,
+        })
     }
+}
 
+pub fn construct_table(prefix: &str, timings: &Timings, style: &TableStyle) -> String {
+    let mut by_duration: Vec<&Timing> = timings.data.iter().collect();
+    by_duration.sort_by(|a, b| {
+        b.total_nanos()
+            .cmp(&a.total_nanos())
+            .then(a.day.cmp(&b.day))
+    });
+
+    let highlighted: Vec<Day> = by_duration
+        .iter()
+        .take(style.highlight)
+        .map(|timing| timing.day)
+        .collect();
+
+    let rows_in_order = match style.sort {
+        Sort::Day => {
+            let mut by_day: Vec<&Timing> = timings.data.iter().collect();
+            by_day.sort_by_key(|timing| timing.day);
+            by_day
+        }
+        Sort::Duration => by_duration,
+    };
+
+    let rows: Vec<Vec<String>> = rows_in_order
+        .iter()
+        .map(|timing| {
+            let cells = [format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(timing.day)
+            )]
+            .into_iter()
+            .chain(style.columns.iter().map(|column| column.cell(timing)));
+
+            if highlighted.contains(&timing.day) {
+                cells.map(|cell| format!("**{cell}**")).collect()
+            } else {
+                cells.collect()
+            }
+        })
+        .collect();
+
+    let headers: Vec<&str> = ["Day"]
+        .into_iter()
+        .chain(style.columns.iter().map(|column| column.header()))
+        .collect();
+
+    let mut lines: Vec<String> = vec![format!("{prefix} Benchmarks"), String::new()];
+    lines.extend(markdown_table(&headers, &rows));
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+
+    if let Some(environment) = timings.environment() {
+        lines.push(String::new());
+        lines.push(format!("_Measured on {}._", environment.describe_build()));
+    }
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+#[cfg(feature = "test_lib")]
+fn update_content(s: &mut String, timings: &Timings) -> Result<(), Error> {
+    readme::update_section(
+        s,
+        SECTION,
+        &construct_table("##", timings, &TableStyle::default()),
+    )
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+/// Returns the days that are listed in the benchmarking table of the readme.
+pub fn listed_days(readme: &str) -> Result<Vec<Day>, Error> {
+    let table = readme::section_content(readme, SECTION)?
+        .ok_or_else(|| Error::Parser(format!("{SECTION}: could not find marker in README.")))?;
+
+    Ok(table
+        .lines()
+        .filter_map(|line| {
+            line.trim_start_matches(['|', ' ', '*'])
+                .strip_prefix("[Day ")
+        })
+        .filter_map(|rest| rest.split(']').next()?.parse::<u8>().ok())
+        .filter_map(Day::new)
+        .collect())
+}
+
+pub fn read_listed_days() -> Result<Vec<Day>, Error> {
+    listed_days(&readme::read()?)
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{construct_table, listed_days, update_content, Column, Sort, TableStyle};
+    use crate::{
+        day,
+        template::environment::Environment,
+        template::timings::{Stats, Timing, Timings},
+    };
+    use std::time::Duration;
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(20).into()),
+                    measured_at: None,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(Duration::from_millis(30).into()),
+                    part_2: Some(Duration::from_millis(40).into()),
+                    measured_at: None,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(Duration::from_millis(40).into()),
+                    part_2: Some(Duration::from_millis(50).into()),
+                    measured_at: None,
+                    environment: None,
                 },
             ],
         }
@@ -131,28 +311,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +340,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "|           Day            |  Part 1  |  Part 2  |",
+            "|:------------------------:|:--------:|:--------:|",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +360,125 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn sorts_and_highlights_benchmarks() {
+        let style = TableStyle {
+            columns: vec![Column::Total],
+            sort: Sort::Duration,
+            highlight: 1,
+        };
+
+        let table = construct_table("##", &get_mock_timings(), &style);
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(lines[2], "|             Day              |    Total     |");
+        assert_eq!(lines[4], "| **[Day 4](./src/bin/04.rs)** | **`90.0ms`** |");
+        assert_eq!(lines[5], "|   [Day 2](./src/bin/02.rs)   |   `70.0ms`   |");
+        assert_eq!(lines[6], "|   [Day 1](./src/bin/01.rs)   |   `30.0ms`   |");
+    }
+
+    #[test]
+    fn renders_stable_output() {
+        let style = TableStyle::default();
+        let mut timings = get_mock_timings();
+        let table = construct_table("##", &timings, &style);
+
+        timings.data.reverse();
+        assert_eq!(construct_table("##", &timings, &style), table);
+    }
+
+    #[test]
+    fn renders_environment_footer() {
+        let environment = Environment {
+            cores: Some(8),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            ..Environment::default()
+        };
+
+        let mut timings = get_mock_timings();
+        for (i, timing) in timings.data.iter_mut().enumerate() {
+            // commit and date differ between runs, but must not change the readme.
+            timing.environment = Some(Environment {
+                commit: Some(format!("abc{i}")),
+                timestamp: i as u64 * 86_400,
+                ..environment.clone()
+            });
+        }
+
+        let table = construct_table("##", &timings, &TableStyle::default());
+        assert_eq!(
+            table.lines().last(),
+            Some("_Measured on 8 cores · release, opt-level 3._")
+        );
+
+        timings.data[1].environment = Some(Environment {
+            cores: Some(4),
+            ..environment
+        });
+        let table = construct_table("##", &timings, &TableStyle::default());
+        assert!(!table.contains("_Measured on"));
+    }
+
+    #[test]
+    fn parses_style_options() {
+        assert_eq!("total".parse::<Column>(), Ok(Column::Total));
+        assert_eq!(" duration".parse::<Sort>(), Ok(Sort::Duration));
+        assert_eq!("memory".parse::<Column>(), Ok(Column::Memory));
+        assert_eq!("median".parse::<Column>(), Ok(Column::Median));
+        assert_eq!("samples".parse::<Column>(), Ok(Column::Samples));
+        assert!("parse_time".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn renders_median_and_samples() {
+        let stats = |samples: u64, median_millis: u64| Stats {
+            samples,
+            median_nanos: median_millis * 1_000_000,
+            min_nanos: 0,
+            max_nanos: 0,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(stats(100, 9));
+        timings.data[0].part_2.as_mut().unwrap().stats = Some(stats(50, 19));
+        timings.data[1].part_2.as_mut().unwrap().stats = Some(stats(25, 38));
+
+        let style = TableStyle {
+            columns: vec![Column::Median, Column::Samples],
+            ..TableStyle::default()
+        };
+
+        let table = construct_table("##", &timings, &style);
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(lines[2], "|           Day            |      Median      |  Samples   |");
+        assert_eq!(lines[4], "| [Day 1](./src/bin/01.rs) | `9.0ms / 19.0ms` | `100 / 50` |");
+        assert_eq!(lines[5], "| [Day 2](./src/bin/02.rs) |   `- / 38.0ms`   |  `- / 25`  |");
+        assert_eq!(lines[6], "| [Day 4](./src/bin/04.rs) |        -         |     -      |");
+    }
+
+    #[test]
+    fn lists_highlighted_days_in_table() {
+        let style = TableStyle {
+            highlight: 3,
+            ..TableStyle::default()
+        };
+
+        let table = construct_table("##", &get_mock_timings(), &style);
+        assert_eq!(
+            listed_days(&format!("{MARKER}\n{table}\n{MARKER}"))
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn lists_days_in_table() {
+        let mut s = format!("foo\n[Day 3](./src/bin/03.rs)\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_timings()).unwrap();
+        assert_eq!(listed_days(&s).unwrap(), vec![day!(1), day!(2), day!(4)]);
+    }
 }
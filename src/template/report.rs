@@ -0,0 +1,593 @@
+/// Module that renders stored timings into a self-contained HTML report with SVG charts,
+/// and into a PNG chart that can be embedded in the readme.
+/// Durations are drawn on a log scale, as they commonly span several orders of magnitude.
+use image::{Rgb, RgbImage};
+
+use crate::template::{
+    aoc_cli,
+    history::History,
+    timings::{PartTiming, Timing, Timings},
+    Day,
+};
+
+pub static HTML_PATH: &str = "./data/report.html";
+pub static PNG_PATH: &str = "./data/report.png";
+
+const PART_COLORS: [[u8; 3]; 2] = [[0x4e, 0x79, 0xa7], [0xf2, 0x8e, 0x2b]];
+const GRID_COLOR: [u8; 3] = [0xdd, 0xdd, 0xdd];
+const TEXT_COLOR: [u8; 3] = [0x44, 0x44, 0x44];
+
+const LABEL_WIDTH: u32 = 64;
+const CHART_WIDTH: u32 = 560;
+const MARGIN: u32 = 24;
+const AXIS_HEIGHT: u32 = 24;
+const BAR_HEIGHT: u32 = 12;
+const DAY_GAP: u32 = 8;
+const HISTORY_HEIGHT: u32 = 240;
+
+/// Maps durations onto a log scale whose bounds are whole decades of nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LogScale {
+    min_decade: i32,
+    max_decade: i32,
+}
+
+impl LogScale {
+    fn new(nanos: impl Iterator<Item = u64>) -> Self {
+        let (min, max) = nanos
+            .map(log10)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), log| {
+                (min.min(log), max.max(log))
+            });
+
+        if min > max {
+            return Self {
+                min_decade: 0,
+                max_decade: 9,
+            };
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let (min_decade, max_decade) = (min.floor() as i32, max.ceil() as i32);
+
+        Self {
+            min_decade,
+            max_decade: max_decade.max(min_decade + 1),
+        }
+    }
+
+    /// Position of `nanos` on the scale, between `0.0` and `1.0`.
+    fn position(self, nanos: u64) -> f64 {
+        let span = f64::from(self.max_decade - self.min_decade);
+        ((log10(nanos) - f64::from(self.min_decade)) / span).clamp(0.0, 1.0)
+    }
+
+    fn decades(self) -> impl Iterator<Item = i32> {
+        self.min_decade..=self.max_decade
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn log10(nanos: u64) -> f64 {
+    (nanos.max(1) as f64).log10()
+}
+
+/// Formats `10^decade` nanoseconds, e.g. `100µs`.
+fn format_decade(decade: i32) -> String {
+    const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+
+    let unit = decade.clamp(0, 9) / 3;
+    let zeros = usize::try_from(decade - unit * 3).unwrap_or_default();
+
+    #[allow(clippy::cast_sign_loss)]
+    let unit = UNITS[unit as usize];
+
+    format!("1{}{unit}", "0".repeat(zeros))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A filled rectangle of a chart, in pixels.
+#[derive(Clone, Debug, PartialEq)]
+struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    color: [u8; 3],
+    title: String,
+}
+
+/// Geometry of the bar chart with one row per day and one bar per part.
+struct BarChart {
+    scale: LogScale,
+    timings: Vec<Timing>,
+}
+
+impl BarChart {
+    fn new(timings: &Timings) -> Self {
+        let mut timings = timings.data.clone();
+        timings.sort_by_key(|timing| timing.day);
+
+        let scale = LogScale::new(
+            timings
+                .iter()
+                .flat_map(|timing| [timing.part_1, timing.part_2])
+                .flatten()
+                .map(|part| part.nanos),
+        );
+
+        Self { scale, timings }
+    }
+
+    fn width() -> u32 {
+        LABEL_WIDTH + CHART_WIDTH + MARGIN
+    }
+
+    fn height(&self) -> u32 {
+        let rows = u32::try_from(self.timings.len()).unwrap_or(u32::MAX);
+        AXIS_HEIGHT + rows * (2 * BAR_HEIGHT + DAY_GAP)
+    }
+
+    fn row_y(index: usize) -> u32 {
+        let index = u32::try_from(index).unwrap_or(u32::MAX);
+        AXIS_HEIGHT + index * (2 * BAR_HEIGHT + DAY_GAP)
+    }
+
+    fn x(&self, nanos: u64) -> u32 {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let offset = (self.scale.position(nanos) * f64::from(CHART_WIDTH)).round() as u32;
+        LABEL_WIDTH + offset
+    }
+
+    /// Vertical gridlines with their label, one per decade.
+    fn gridlines(&self) -> Vec<(u32, String)> {
+        self.scale
+            .decades()
+            .map(|decade| {
+                (
+                    self.x(10_u64.pow(decade.unsigned_abs())),
+                    format_decade(decade),
+                )
+            })
+            .collect()
+    }
+
+    /// Labels of the rows, with the vertical center of the row.
+    fn day_labels(&self) -> Vec<(u32, Day)> {
+        self.timings
+            .iter()
+            .enumerate()
+            .map(|(i, timing)| (Self::row_y(i) + BAR_HEIGHT, timing.day))
+            .collect()
+    }
+
+    fn bars(&self) -> Vec<Rect> {
+        let bar = |y: u32, part: u8, timing: &PartTiming, day: Day| Rect {
+            x: LABEL_WIDTH,
+            y,
+            width: (self.x(timing.nanos) - LABEL_WIDTH).max(1),
+            height: BAR_HEIGHT - 2,
+            color: PART_COLORS[usize::from(part - 1)],
+            title: format!("Day {day} · Part {part}: {}", timing.format()),
+        };
+
+        self.timings
+            .iter()
+            .enumerate()
+            .flat_map(|(i, timing)| {
+                let y = Self::row_y(i);
+                [
+                    timing.part_1.map(|part| bar(y, 1, &part, timing.day)),
+                    timing
+                        .part_2
+                        .map(|part| bar(y + BAR_HEIGHT, 2, &part, timing.day)),
+                ]
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders a self-contained HTML page with a bar chart of `timings`,
+/// and a chart of each day's total over time if `history` holds more than one run.
+pub fn render_html(timings: &Timings, history: &History) -> String {
+    let title = match aoc_cli::get_year() {
+        Some(year) => format!("Advent of Code {year} · Benchmarks"),
+        None => "Advent of Code · Benchmarks".into(),
+    };
+
+    let mut body = vec![
+        format!("<h1>{}</h1>", escape(&title)),
+        format!(
+            "<p><strong>Total: {:.2}ms</strong></p>",
+            timings.total_millis()
+        ),
+    ];
+
+    if let Some(environment) = timings.environment() {
+        body.push(format!(
+            "<p class=\"environment\">Measured on {}.</p>",
+            escape(&environment.describe())
+        ));
+    }
+
+    body.push("<h2>Timings</h2>".into());
+    body.push(format!(
+        "<p class=\"legend\"><span style=\"background:{}\"></span>Part 1 <span style=\"background:{}\"></span>Part 2</p>",
+        hex(PART_COLORS[0]),
+        hex(PART_COLORS[1])
+    ));
+    body.push(render_bar_svg(&BarChart::new(timings)));
+
+    if history.runs.len() > 1 {
+        body.push("<h2>History</h2>".into());
+        body.push("<p>Total time of each day per stored benchmark run.</p>".into());
+        body.push(render_history_svg(history));
+    }
+
+    [
+        "<!DOCTYPE html>".to_string(),
+        "<html lang=\"en\">".into(),
+        "<head>".into(),
+        "<meta charset=\"utf-8\">".into(),
+        format!("<title>{}</title>", escape(&title)),
+        "<style>".into(),
+        "body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }".into(),
+        "svg text { font-size: 11px; fill: #444; }".into(),
+        ".environment { color: #666; font-style: italic; }".into(),
+        ".legend span { display: inline-block; width: 10px; height: 10px; margin: 0 4px 0 8px; }"
+            .into(),
+        "</style>".into(),
+        "</head>".into(),
+        "<body>".into(),
+    ]
+    .into_iter()
+    .chain(body)
+    .chain(["</body>".into(), "</html>".into(), String::new()])
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn render_bar_svg(chart: &BarChart) -> String {
+    let mut elements = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+        BarChart::width(),
+        chart.height()
+    )];
+
+    for (x, label) in chart.gridlines() {
+        elements.push(format!(
+            "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"{}\"/>",
+            AXIS_HEIGHT - 4,
+            chart.height(),
+            hex(GRID_COLOR)
+        ));
+        elements.push(format!(
+            "<text x=\"{x}\" y=\"{}\" text-anchor=\"middle\">{label}</text>",
+            AXIS_HEIGHT - 8
+        ));
+    }
+
+    for (y, day) in chart.day_labels() {
+        elements.push(format!(
+            "<text x=\"0\" y=\"{y}\" dominant-baseline=\"middle\">Day {day}</text>"
+        ));
+    }
+
+    for rect in chart.bars() {
+        elements.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}</title></rect>",
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            hex(rect.color),
+            escape(&rect.title)
+        ));
+    }
+
+    elements.push("</svg>".into());
+    elements.join("\n")
+}
+
+fn render_history_svg(history: &History) -> String {
+    let width = LABEL_WIDTH + CHART_WIDTH + MARGIN;
+    let height = HISTORY_HEIGHT + AXIS_HEIGHT;
+
+    let scale = LogScale::new(
+        history
+            .runs
+            .iter()
+            .flat_map(|run| run.data.iter().map(Timing::total_nanos)),
+    );
+
+    let run_count = u32::try_from(history.runs.len() - 1)
+        .unwrap_or(u32::MAX)
+        .max(1);
+    let x = |run: usize| {
+        let run = u32::try_from(run).unwrap_or(u32::MAX);
+        LABEL_WIDTH + run * CHART_WIDTH / run_count
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let y = |nanos: u64| {
+        let offset = (scale.position(nanos) * f64::from(HISTORY_HEIGHT)).round() as u32;
+        HISTORY_HEIGHT - offset + AXIS_HEIGHT / 2
+    };
+
+    let mut elements = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+    )];
+
+    for decade in scale.decades() {
+        let line_y = y(10_u64.pow(decade.unsigned_abs()));
+        elements.push(format!(
+            "<line x1=\"{LABEL_WIDTH}\" y1=\"{line_y}\" x2=\"{}\" y2=\"{line_y}\" stroke=\"{}\"/>",
+            LABEL_WIDTH + CHART_WIDTH,
+            hex(GRID_COLOR)
+        ));
+        elements.push(format!(
+            "<text x=\"0\" y=\"{line_y}\" dominant-baseline=\"middle\">{}</text>",
+            format_decade(decade)
+        ));
+    }
+
+    let mut days: Vec<Day> = history
+        .runs
+        .iter()
+        .flat_map(|run| run.data.iter().map(|timing| timing.day))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    for day in days {
+        let points: Vec<String> = history
+            .runs
+            .iter()
+            .enumerate()
+            .filter_map(|(run, timings)| {
+                let timing = timings.data.iter().find(|timing| timing.day == day)?;
+                Some(format!("{},{}", x(run), y(timing.total_nanos())))
+            })
+            .collect();
+
+        elements.push(format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"><title>Day {day}</title></polyline>",
+            points.join(" "),
+            day_color(day)
+        ));
+    }
+
+    elements.push("</svg>".into());
+    elements.join("\n")
+}
+
+/// A distinct color for every day.
+fn day_color(day: Day) -> String {
+    format!("hsl({}, 60%, 45%)", u32::from(day.into_inner()) * 360 / 25)
+}
+
+fn hex(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the bar chart of `timings` as an image.
+/// Labels are drawn with a small built-in pixel font, so that no font files are needed.
+pub fn render_png(timings: &Timings) -> RgbImage {
+    let chart = BarChart::new(timings);
+    let mut image = RgbImage::from_pixel(BarChart::width(), chart.height(), Rgb([0xff; 3]));
+
+    for (x, label) in chart.gridlines() {
+        fill(
+            &mut image,
+            x,
+            AXIS_HEIGHT - 4,
+            1,
+            chart.height(),
+            GRID_COLOR,
+        );
+        let label_width = text_width(&label);
+        draw_text(&mut image, x.saturating_sub(label_width / 2), 4, &label);
+    }
+
+    for (y, day) in chart.day_labels() {
+        draw_text(
+            &mut image,
+            4,
+            y - GLYPH_HEIGHT * GLYPH_SCALE / 2,
+            &day.to_string(),
+        );
+    }
+
+    for rect in chart.bars() {
+        fill(
+            &mut image,
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            rect.color,
+        );
+    }
+
+    image
+}
+
+fn fill(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+    for px in x..(x + width).min(image.width()) {
+        for py in y..(y + height).min(image.height()) {
+            image.put_pixel(px, py, Rgb(color));
+        }
+    }
+}
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+const GLYPH_SCALE: u32 = 2;
+
+/// Rows of a 3x5 glyph, most significant bit on the left.
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'n' => [0b000, 0b110, 0b101, 0b101, 0b101],
+        'µ' => [0b000, 0b101, 0b101, 0b111, 0b100],
+        'm' => [0b000, 0b111, 0b111, 0b101, 0b101],
+        's' => [0b000, 0b111, 0b110, 0b011, 0b111],
+        _ => return None,
+    })
+}
+
+fn text_width(text: &str) -> u32 {
+    let chars = u32::try_from(text.chars().count()).unwrap_or(u32::MAX);
+    chars * (GLYPH_WIDTH + 1) * GLYPH_SCALE
+}
+
+fn draw_text(image: &mut RgbImage, x: u32, y: u32, text: &str) {
+    for (i, rows) in text.chars().map(glyph).enumerate() {
+        let Some(rows) = rows else {
+            continue;
+        };
+
+        let glyph_x = x + u32::try_from(i).unwrap_or(u32::MAX) * (GLYPH_WIDTH + 1) * GLYPH_SCALE;
+
+        for (row, bits) in (0..).zip(rows) {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                    fill(
+                        image,
+                        glyph_x + column * GLYPH_SCALE,
+                        y + row * GLYPH_SCALE,
+                        GLYPH_SCALE,
+                        GLYPH_SCALE,
+                        TEXT_COLOR,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_decade, render_html, render_png, BarChart, LogScale};
+    use crate::{
+        day,
+        template::{
+            history::History,
+            timings::{Timing, Timings},
+        },
+    };
+    use std::time::Duration;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(2),
+                    part_1: Some(Duration::from_millis(30).into()),
+                    part_2: None,
+                    measured_at: None,
+                    environment: None,
+                },
+                Timing {
+                    day: day!(1),
+                    part_1: Some(Duration::from_nanos(500).into()),
+                    part_2: Some(Duration::from_micros(20).into()),
+                    measured_at: None,
+                    environment: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn computes_log_scale() {
+        let scale = LogScale::new([500, 30_000_000].into_iter());
+        assert_eq!(
+            scale,
+            LogScale {
+                min_decade: 2,
+                max_decade: 8
+            }
+        );
+        assert_eq!(scale.position(100), 0.0);
+        assert_eq!(scale.position(100_000), 0.5);
+        assert_eq!(scale.position(u64::MAX), 1.0);
+
+        let single = LogScale::new([1000].into_iter());
+        assert_eq!(single.max_decade - single.min_decade, 1);
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(6), "1ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn lays_out_bars_by_day() {
+        let chart = BarChart::new(&get_mock_timings());
+        let bars = chart.bars();
+
+        assert_eq!(bars.len(), 3);
+        assert_eq!(bars[0].title, "Day 01 · Part 1: 500.0ns");
+        assert_eq!(bars[2].title, "Day 02 · Part 1: 30.0ms");
+        assert!(bars[0].width < bars[1].width);
+        assert!(bars[1].width < bars[2].width);
+        assert!(bars[2].y > bars[1].y);
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render_html(&get_mock_timings(), &History::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<rect").count(), 3);
+        assert!(html.contains("<title>Day 02 · Part 1: 30.0ms</title>"));
+        assert!(!html.contains("<h2>History</h2>"));
+
+        let history = History {
+            runs: vec![get_mock_timings(), get_mock_timings()],
+        };
+        let html = render_html(&get_mock_timings(), &history);
+        assert_eq!(html.matches("<polyline").count(), 2);
+    }
+
+    #[test]
+    fn renders_png() {
+        let chart = BarChart::new(&get_mock_timings());
+        let image = render_png(&get_mock_timings());
+
+        assert_eq!(image.width(), BarChart::width());
+        assert_eq!(image.height(), chart.height());
+
+        let bar = &chart.bars()[0];
+        assert_eq!(image.get_pixel(bar.x, bar.y).0, bar.color);
+    }
+}
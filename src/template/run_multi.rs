@@ -1,40 +1,139 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, path::PathBuf, time::Duration};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    environment::Environment,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Options that apply to every day of a multi-run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub count_allocations: bool,
+    /// Runs every registered variant of a part, see [`crate::solution`].
+    pub variants: bool,
+    /// Wall-clock limit for running a single day. Does not include compilation.
+    pub timeout: Option<Duration>,
+}
+
+/// The source tree that solutions are built from.
+/// Solutions always run in the current directory, so that they read the inputs of the current checkout.
+#[derive(Clone, Debug)]
+pub struct Checkout {
+    pub root: PathBuf,
+    /// Overrides cargo's target directory, so that builds of different checkouts do not invalidate each other.
+    pub target_dir: Option<PathBuf>,
+}
+
+impl Default for Checkout {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("."),
+            target_dir: None,
+        }
+    }
+}
+
+/// How running the solution of a single day ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    NotScaffolded,
+    /// The binary exited successfully with the given stdout lines.
+    Finished(Vec<String>),
+    BuildFailed,
+    Panicked(String),
+    TimedOut(Duration),
+    Failed(Option<i32>),
+}
+
+impl Outcome {
+    /// Describes why a day failed, or `None` if it did not fail.
+    pub fn failure(&self) -> Option<String> {
+        match self {
+            Outcome::NotScaffolded | Outcome::Finished(_) => None,
+            Outcome::BuildFailed => Some("failed to build.".into()),
+            Outcome::Panicked(message) => Some(format!("panicked: {message}")),
+            Outcome::TimedOut(timeout) => Some(format!("timed out after {timeout:?}.")),
+            Outcome::Failed(Some(code)) => Some(format!("exited with code {code}.")),
+            Outcome::Failed(None) => Some("was terminated by a signal.".into()),
+        }
+    }
+}
+
+pub fn run_multi(days_to_run: &HashSet<Day>, options: RunOptions) -> Option<Timings> {
+    run_multi_in(&Checkout::default(), days_to_run, options)
+}
+
+/// Like [`run_multi`], but builds the solutions of another checkout.
+pub fn run_multi_in(
+    checkout: &Checkout,
+    days_to_run: &HashSet<Day>,
+    options: RunOptions,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failures: Vec<(Day, String)> = vec![];
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
+    let scaffolded: Vec<Day> = days
+        .iter()
+        .copied()
+        .filter(|day| checkout.root.join(get_path_for_bin(*day)).exists())
+        .collect();
+
+    let executables = child_commands::build(checkout, &scaffolded, options).unwrap();
+
+    let environment = options
+        .is_timed
+        .then(|| Environment::capture(&checkout.root, options.is_release));
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    days.iter().for_each(|&day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let outcome = match executables.get(&day) {
+            Some(executable) => child_commands::run_solution(executable, options).unwrap(),
+            None if scaffolded.contains(&day) => Outcome::BuildFailed,
+            None => Outcome::NotScaffolded,
+        };
 
-            if output.is_empty() {
+        match outcome {
+            Outcome::Finished(output) if !output.is_empty() => {
+                timings.push(Timing {
+                    environment: environment.clone(),
+                    ..child_commands::parse_exec_time(&output, day)
+                });
+            }
+            Outcome::NotScaffolded | Outcome::Finished(_) => {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
-        });
+            outcome => {
+                let reason = outcome.failure().unwrap_or_default();
+                println!("{ANSI_BOLD}Failed:{ANSI_RESET} {reason}");
+                failures.push((day, reason));
+            }
+        }
+    });
 
-    if is_timed {
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed days:{ANSI_RESET}");
+        for (day, reason) in &failures {
+            println!("  Day {day}: {reason}");
+        }
+    }
+
+    if options.is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -67,39 +166,110 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Checkout, Error, Outcome, RunOptions};
+    use crate::template::{
+        runner::TIMING_LINE_PREFIX,
+        timings::{parse_formatted_duration, PartTiming},
+        Day,
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant, SystemTime},
     };
+    use tinyjson::JsonValue;
+
+    /// Builds the solution bins for the given days and returns the paths of their executables.
+    /// Days that fail to compile are missing from the result, the compiler errors are forwarded to stderr.
+    pub fn build(
+        checkout: &Checkout,
+        days: &[Day],
+        options: RunOptions,
+    ) -> Result<HashMap<Day, PathBuf>, Error> {
+        if days.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--keep-going".into(),
+            "--message-format=json-render-diagnostics".into(),
+        ];
+
+        if options.is_release {
+            args.push("--release".into());
+        }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if options.count_allocations {
+            args.push("--features".into());
+            args.push("count-allocations".into());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if let Some(target_dir) = &checkout.target_dir {
+            args.push("--target-dir".into());
+            args.push(target_dir.to_string_lossy().into_owned());
+        }
 
-        if is_release {
-            args.push("--release");
+        for day in days {
+            args.push("--bin".into());
+            args.push(day.to_string());
         }
 
-        if is_timed {
+        let output = Command::new("cargo")
+            .args(&args)
+            .current_dir(&checkout.root)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        Ok(parse_build_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Collects the executables of solution bins from the JSON messages of `cargo build`.
+    fn parse_build_output(stdout: &str) -> HashMap<Day, PathBuf> {
+        stdout
+            .lines()
+            .filter_map(|line| line.parse::<JsonValue>().ok())
+            .filter_map(|message| {
+                let message = message.get::<HashMap<String, JsonValue>>()?;
+
+                if message.get("reason")?.get::<String>()? != "compiler-artifact" {
+                    return None;
+                }
+
+                let executable = message.get("executable")?.get::<String>()?;
+                let name = message
+                    .get("target")?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()?;
+
+                Some((Day::from_str(name).ok()?, PathBuf::from(executable)))
+            })
+            .collect()
+    }
+
+    /// Run the solution executable for a given day.
+    pub fn run_solution(executable: &Path, options: RunOptions) -> Result<Outcome, Error> {
+        let mut args = vec![];
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if options.variants {
+            args.push("--variants");
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing the lines of both.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,24 +278,75 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| eprintln!("{line}"))
+                .collect::<Vec<_>>()
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| {
+                    if !line.starts_with(TIMING_LINE_PREFIX) {
+                        println!("{line}");
+                    }
+                })
+                .collect::<Vec<_>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let status = wait_with_timeout(&mut cmd, options.timeout)?;
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let output = stdout_thread.join().unwrap();
+        let errors = stderr_thread.join().unwrap().join("\n");
 
-        Ok(output)
+        Ok(match (status, options.timeout) {
+            (Some(status), _) if status.success() => Outcome::Finished(output),
+            (Some(status), _) => match parse_panic_message(&errors) {
+                Some(message) => Outcome::Panicked(message),
+                None => Outcome::Failed(status.code()),
+            },
+            (None, timeout) => Outcome::TimedOut(timeout.unwrap_or_default()),
+        })
+    }
+
+    /// Waits for the child to exit. Kills it and returns `None` if it runs longer than `timeout`.
+    fn wait_with_timeout(
+        cmd: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(Some(cmd.wait()?));
+        };
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if Instant::now() >= deadline {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Extracts the message of a panic from the stderr output of a binary.
+    pub fn parse_panic_message(stderr: &str) -> Option<String> {
+        let mut lines = stderr
+            .lines()
+            .skip_while(|line| !line.contains("panicked at"));
+        lines.next()?;
+        let message = lines.next()?.trim();
+        (!message.is_empty()).then(|| message.to_string())
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,110 +354,136 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            measured_at: Some(SystemTime::now()),
+            environment: None,
         };
 
         output
             .iter()
+            .filter_map(|l| l.strip_prefix(TIMING_LINE_PREFIX))
             .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let parsed = parse_timing_line(l);
+                if parsed.is_none() {
                     eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
                 }
-
-                timings.total_nanos += nanos;
+                parsed
+            })
+            .for_each(|(part, timing)| match part {
+                1 => timings.part_1 = Some(timing),
+                2 => timings.part_2 = Some(timing),
+                _ => {}
             });
 
+        if timings.part_1.is_none() && timings.part_2.is_none() {
+            return parse_legacy_exec_time(output, day);
+        }
+
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Reads the formatted durations of runners that do not print timing lines yet,
+    /// e.g. when benching an older revision. Lines look like `Part 1: 42 (74.1ns @ 100 samples)`.
+    fn parse_legacy_exec_time(output: &[String], day: Day) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            measured_at: Some(SystemTime::now()),
+            environment: None,
+        };
+
+        for line in output.iter().filter(|l| l.contains(" samples)")) {
+            let Some(nanos) = line
+                .split(" samples)")
+                .next()
+                .and_then(|l| l.split('(').next_back())
+                .and_then(|l| l.split('@').next())
+                .and_then(|l| parse_formatted_duration(l.trim()))
+            else {
+                eprintln!("Could not parse timings from line: {line}");
+                continue;
+            };
+
+            let timing = Some(Duration::from_nanos(nanos).into());
+
+            match line.split(':').next() {
+                Some(part) if part.contains("Part 1") => timings.part_1 = timing,
+                Some(part) if part.contains("Part 2") => timings.part_2 = timing,
+                _ => {}
+            }
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timings
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Parses the part and timing of a line printed by the runner, e.g. `1 {"nanos": 74}`.
+    fn parse_timing_line(line: &str) -> Option<(u8, PartTiming)> {
+        let (part, json) = line.trim().split_once(' ')?;
+        let json = json.parse::<JsonValue>().ok()?;
+        Some((part.parse().ok()?, PartTiming::try_from(&json).ok()?))
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_build_output, parse_exec_time, parse_panic_message};
+        use std::path::PathBuf;
 
-        use crate::day;
+        use crate::{day, template::timings::Stats};
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 1: 0 (74.0ns @ 100000 samples)".into(),
+                    r#"::timing:: 1 {"nanos":74,"samples":100000,"median_nanos":70,"min_nanos":60,"max_nanos":900}"#.into(),
+                    "Part 2: 10 (74.1ms @ 99 samples)".into(),
+                    r#"::timing:: 2 {"nanos":74130000,"samples":99,"median_nanos":74000000,"min_nanos":73000000,"max_nanos":80000000}"#.into(),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos(), 74130074);
+            assert_eq!(res.part_1.unwrap().format(), "74.0ns");
+            assert_eq!(
+                res.part_2.unwrap().stats,
+                Some(Stats {
+                    samples: 99,
+                    median_nanos: 74_000_000,
+                    min_nanos: 73_000_000,
+                    max_nanos: 80_000_000
+                })
+            );
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
+        fn ignores_patterns_in_output() {
             let res = parse_exec_time(
                 &[
                     "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
+                    "::timing:: in the answer".into(),
+                    r#"::timing:: 2 {"nanos":100000000}"#.into(),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos(), 100_000_000);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap().format(), "100.0ms");
+        }
+
+        #[test]
+        fn parses_legacy_execution_times() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
+                    "Part 2: 10s (100.5ms @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos(), 2_100_500_000);
+            assert_eq!(res.part_1.unwrap().format(), "2.0s");
+            assert_eq!(res.part_2.unwrap().format(), "100.5ms");
         }
 
         #[test]
@@ -249,9 +496,37 @@ pub mod child_commands {
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos(), 0);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_panic_message() {
+            let stderr = "thread 'main' panicked at src/template/mod.rs:24:7:\ncould not open input file\nnote: run with `RUST_BACKTRACE=1`";
+            assert_eq!(
+                parse_panic_message(stderr),
+                Some("could not open input file".into())
+            );
+            assert_eq!(parse_panic_message("warning: unused variable"), None);
+        }
+
+        #[test]
+        fn parses_build_output() {
+            let stdout = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"01","kind":["bin"]},"executable":"/repo/target/release/01"}"#,
+                r#"{"reason":"compiler-message","target":{"name":"02","kind":["bin"]}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+            ]
+            .join("\n");
+
+            let executables = parse_build_output(&stdout);
+            assert_eq!(executables.len(), 1);
+            assert_eq!(
+                executables.get(&day!(1)),
+                Some(&PathBuf::from("/repo/target/release/01"))
+            );
+        }
     }
 }
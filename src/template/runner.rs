@@ -2,67 +2,281 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::path::Path;
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use image::ImageResult;
+use tinyjson::JsonValue;
+
+use crate::template::{alloc, readme};
+use crate::template::submissions::{Submission, Submissions, Verdict};
+use crate::template::timings::{Memory, PartTiming, Stats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, output_dir, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Prefix of the machine-readable timing lines that are printed when running with `--time`.
+pub const TIMING_LINE_PREFIX: &str = "::timing::";
+
+/// Name of the implementation that is registered as `part_one` and `part_two`.
+pub const DEFAULT_VARIANT: &str = "default";
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    run_part_labeled(func, input, day, part, &format!("Part {part}"), true);
+}
+
+/// Runs a part and returns its formatted result.
+/// Only the default implementation of a part prints a timing line and may be submitted.
+fn run_part_labeled<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    label: &str,
+    is_default: bool,
+) -> Option<String> {
+    let (result, timing) = run_timed(func, input, |result| print_result(result, label, ""));
+
+    print_result(&result, label, &format_duration(&timing));
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if result.is_some() && is_timed() && is_default {
+        print_timing_line(part, &timing);
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let formatted = result.as_ref().map(ToString::to_string);
 
-    if let Some(result) = result {
+    if let (Some(result), true) = (result, is_default) {
         submit_result(result, day, part);
     }
+
+    formatted
+}
+
+/// Solves a part and returns the formatted result.
+type Solve = Box<dyn Fn(&str) -> Option<String>>;
+/// Runs a part with a label and returns the formatted result.
+type Run = Box<dyn Fn(&str, &str) -> Option<String>>;
+
+/// An implementation of a part, registered with `solution!(DD, variants = [...])`.
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    /// Solves the part without any output.
+    pub(crate) solve: Solve,
+    /// Runs the part like [`run_part`] with the given label.
+    run: Run,
+}
+
+impl Variant {
+    pub fn new<T: Display + 'static>(
+        day: Day,
+        part: u8,
+        name: &'static str,
+        func: impl Fn(&str) -> Option<T> + Copy + 'static,
+    ) -> Self {
+        Self {
+            part,
+            name,
+            solve: Box::new(move |input| func(input).map(|result| result.to_string())),
+            run: Box::new(move |input, label| run_part_labeled(func, input, day, part, label, name == DEFAULT_VARIANT)),
+        }
+    }
+}
+
+/// Runs the registered variants of a solution, depending on the arguments:
+///  1. `--variants` runs every variant of every part and checks that they agree.
+///  2. `--variant <name>` runs the named variant of the parts that have one and the default of the others.
+///  3. otherwise, the default variants are run.
+pub fn run_variants(input: &str, variants: &[Variant]) {
+    let args: Vec<String> = env::args().collect();
+    let mut parts: Vec<u8> = variants.iter().map(|v| v.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    if args.iter().any(|x| x == "--variants") {
+        let results: Vec<(&Variant, Option<String>)> = parts
+            .iter()
+            .flat_map(|part| variants.iter().filter(move |v| v.part == *part))
+            .map(|variant| {
+                let label = format!("Part {} ({})", variant.part, variant.name);
+                (variant, (variant.run)(input, &label))
+            })
+            .collect();
+
+        let mismatches = find_mismatches(&results);
+        if !mismatches.is_empty() {
+            eprintln!("{ANSI_BOLD}Variants disagree:{ANSI_RESET}\n{}", mismatches.join("\n"));
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    let selected = args
+        .iter()
+        .position(|x| x == "--variant")
+        .map(|i| args.get(i + 1).map_or("", String::as_str));
+
+    if let Some(name) = selected {
+        if !variants.iter().any(|v| v.name == name) {
+            let mut names: Vec<&str> = variants.iter().map(|v| v.name).collect();
+            names.sort_unstable();
+            names.dedup();
+            eprintln!("Unknown variant `{name}`, expected one of: {}.", names.join(", "));
+            process::exit(1);
+        }
+    }
+
+    for part in parts {
+        let find = |name: &str| variants.iter().find(|v| v.part == part && v.name == name);
+
+        let variant = selected.and_then(find).or_else(|| find(DEFAULT_VARIANT));
+
+        if let Some(variant) = variant {
+            let label = match variant.name {
+                DEFAULT_VARIANT => format!("Part {part}"),
+                name => format!("Part {part} ({name})"),
+            };
+            (variant.run)(input, &label);
+        }
+    }
+}
+
+/// Checks that all variants of a part return the same result for `input`.
+pub fn check_variants(input: &str, variants: &[Variant]) -> Result<(), String> {
+    let results: Vec<(&Variant, Option<String>)> = variants.iter().map(|variant| (variant, (variant.solve)(input))).collect();
+
+    let mismatches = find_mismatches(&results);
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches.join("\n"))
+    }
+}
+
+/// Compares the results of every variant to the first variant of the same part.
+fn find_mismatches(results: &[(&Variant, Option<String>)]) -> Vec<String> {
+    let format = |result: &Option<String>| result.clone().unwrap_or_else(|| "✖".into());
+
+    results
+        .iter()
+        .filter_map(|(variant, result)| {
+            let (expected, expected_result) = results.iter().find(|(v, _)| v.part == variant.part)?;
+
+            (result != expected_result).then(|| {
+                format!(
+                    "  Part {}: `{}` returned {}, `{}` returned {}.",
+                    variant.part,
+                    variant.name,
+                    format(result),
+                    expected.name,
+                    format(expected_result)
+                )
+            })
+        })
+        .collect()
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// Whether a part is currently run repeatedly to bench it.
+static BENCHING: AtomicBool = AtomicBool::new(false);
+
+/// Returns `true` while the bench iterations of a part run. Solutions should not produce any extra output then.
+pub fn is_benching() -> bool {
+    BENCHING.load(Ordering::Relaxed)
+}
+
+/// Whether the solution was started with `--visualize`. Read once, as collecting the arguments allocates.
+static VISUALIZE: OnceLock<bool> = OnceLock::new();
+
+/// Returns `true` if the solution was started with `--visualize`, e.g. via `cargo solve 15 --visualize`.
+/// Always `false` during bench iterations, so that visualizations do not skew timings.
+pub fn is_visualizing() -> bool {
+    !is_benching() && *VISUALIZE.get_or_init(|| env::args().any(|x| x == "--visualize"))
+}
+
+/// Returns `true` if the solution was started with `--step`, which steps through its simulation instead of solving.
+/// See [`Simulation`](crate::utils::simulation::Simulation).
+pub fn is_stepping() -> bool {
+    env::args().any(|x| x == "--step")
+}
+
+/// Writes images of a solution for an input into the given directory, see [`crate::utils::viz`].
+pub type Render = fn(&str, &Path) -> ImageResult<()>;
+
+/// Returns `true` if the solution was started with `--render`, which writes images instead of solving.
+pub fn is_rendering() -> bool {
+    env::args().any(|x| x == "--render")
+}
+
+/// Renders `input` into the output directory of `day` and reports where the images went.
+pub fn run_render(day: Day, render: Render, input: &str) {
+    let dir = output_dir(day);
+
+    match render(input, &dir) {
+        Ok(()) => println!("Rendered day {day} to {ANSI_BOLD}{}{ANSI_RESET}", dir.display()),
+        Err(e) => {
+            eprintln!("Failed to render day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Reports that `cargo solve --render` was used for a day without a render function and exits.
+pub fn missing_render(day: Day) -> ! {
+    eprintln!(
+        "Day {day} does not render any images. Register a function with `solution!({}, render = render)`.",
+        day.into_inner()
+    );
+    process::exit(1);
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, PartTiming) {
+    // read the flag before measuring, so that its allocations are not counted for the solution.
+    is_visualizing();
+
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let samples = if is_timed() { bench(func, input, &base_time) } else { vec![base_time] };
 
-    (result, run.0, run.1)
+    (result, part_timing(&samples, memory))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Vec<Duration> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
+    // solutions must not log or visualize while they are benched, as this would skew the timings.
+    let log_level = log::max_level();
+    log::set_max_level(log::LevelFilter::Off);
+    BENCHING.store(true, Ordering::Relaxed);
+
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
@@ -71,26 +285,41 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
+    BENCHING.store(false, Ordering::Relaxed);
+    log::set_max_level(log_level);
+
+    timers
+}
+
+fn part_timing(samples: &[Duration], memory: Option<Memory>) -> PartTiming {
+    PartTiming {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        nanos: average_duration(samples) as u64,
+        stats: Stats::from_samples(samples),
+        memory,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    numbers.iter().map(std::time::Duration::as_nanos).sum::<u128>() / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(timing: &PartTiming) -> String {
+    let duration = match timing.stats {
+        Some(stats) if stats.samples > 1 => format!("{} @ {} samples", timing.format(), stats.samples),
+        _ => timing.format(),
+    };
+
+    match timing.memory {
+        Some(memory) => format!(" ({duration} · {})", memory.format()),
+        None => format!(" ({duration})"),
+    }
+}
+
+/// Prints the timing of a part as JSON, so that `run_multi` does not need to parse the formatted duration.
+fn print_timing_line(part: u8, timing: &PartTiming) {
+    if let Ok(json) = JsonValue::from(timing).stringify() {
+        println!("{TIMING_LINE_PREFIX} {part} {json}");
     }
 }
 
@@ -165,5 +394,48 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let output = aoc_cli::submit(day, part, &answer);
+
+    if let Ok(output) = &output {
+        let response = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+        let verdict = Verdict::from_response(&response);
+        let submission = Submission { day, part, answer, verdict };
+
+        if let Err(e) = Submissions::record(submission) {
+            eprintln!("Failed to record submission: {e}");
+        } else if verdict == Verdict::Correct {
+            match readme::registered_sections().update_file(&readme::Context::read()) {
+                Ok(sections) if !sections.is_empty() => println!("Updated readme sections: {}.", sections.join(", ")),
+                Ok(_) => {}
+                Err(_) => eprintln!("Failed to update readme."),
+            }
+        }
+    }
+
+    Some(output)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_variants, Variant, DEFAULT_VARIANT};
+    use crate::day;
+
+    #[test]
+    fn checks_that_variants_agree() {
+        let variants = vec![
+            Variant::new(day!(1), 1, DEFAULT_VARIANT, |input: &str| Some(input.len())),
+            Variant::new(day!(1), 1, "bytes", |input: &str| Some(input.bytes().count())),
+            Variant::new(day!(1), 2, DEFAULT_VARIANT, |_: &str| Some(1)),
+            Variant::new(day!(1), 2, "unsolved", |_: &str| None::<u32>),
+        ];
+
+        assert_eq!(check_variants("abc", &variants[..3]), Ok(()));
+        assert_eq!(
+            check_variants("abc", &variants),
+            Err("  Part 2: `unsolved` returned ✖, `default` returned 1.".into())
+        );
+    }
 }
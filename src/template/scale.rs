@@ -0,0 +1,251 @@
+/// Module that benches a solution on generated inputs of growing size and estimates how its run time scales.
+/// Days opt in by registering a generator with `solution!(DD, generator = generate)`.
+use std::{
+    env,
+    hint::black_box,
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    readme::markdown_table,
+    runner::{Variant, DEFAULT_VARIANT},
+    timings::format_bytes,
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+use crate::utils::random::Rng;
+
+/// Generates an input of the given size. The meaning of the size is up to the day, e.g. the side length of a grid.
+pub type Generator = fn(usize, &mut Rng) -> String;
+
+const SEED: u64 = 2024;
+const MIN_SIZE: usize = 8;
+const MAX_STEPS: u32 = 12;
+/// Sizes stop growing once a single run of a part takes longer than this.
+const RUN_LIMIT: Duration = Duration::from_secs(1);
+/// Time spent sampling a part at every size.
+const SAMPLE_BUDGET: Duration = Duration::from_millis(200);
+const MAX_SAMPLES: usize = 50;
+
+pub fn is_scaling() -> bool {
+    env::args().any(|x| x == "--scale")
+}
+
+/// The median run time of every part at one input size.
+#[derive(Clone, Debug, PartialEq)]
+struct Measurement {
+    size: usize,
+    input_bytes: usize,
+    /// `None` if the part was not run at this size, because it exceeded the run limit at a smaller size.
+    parts: Vec<Option<Duration>>,
+}
+
+pub fn run_scaling(day: Day, generator: Option<Generator>, variants: &[Variant]) {
+    let Some(generator) = generator else {
+        eprintln!("Day {day} does not have a generator. Register one with `solution!({}, generator = generate)`.", day.into_inner());
+        process::exit(1);
+    };
+
+    let parts: Vec<&Variant> = variants
+        .iter()
+        .filter(|variant| variant.name == DEFAULT_VARIANT)
+        .collect();
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} · scaling with seed {SEED}");
+    println!("------");
+
+    let mut measurements: Vec<Measurement> = vec![];
+    let mut exceeded = vec![false; parts.len()];
+
+    for step in 0..MAX_STEPS {
+        if exceeded.iter().all(|x| *x) {
+            break;
+        }
+
+        let size = MIN_SIZE << step;
+        let input = generator(size, &mut Rng::new(SEED ^ size as u64));
+
+        let durations: Vec<Option<Duration>> = parts
+            .iter()
+            .zip(exceeded.iter_mut())
+            .map(|(part, exceeded)| {
+                if *exceeded {
+                    return None;
+                }
+
+                let duration = measure(|| (part.solve)(&input));
+                *exceeded = duration > RUN_LIMIT;
+                Some(duration)
+            })
+            .collect();
+
+        println!(
+            "n = {size}: {}",
+            durations
+                .iter()
+                .map(|d| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}")))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        measurements.push(Measurement {
+            size,
+            input_bytes: input.len(),
+            parts: durations,
+        });
+    }
+
+    let headers: Vec<String> = ["n".to_string(), "Input".into()]
+        .into_iter()
+        .chain(parts.iter().map(|part| format!("Part {}", part.part)))
+        .collect();
+
+    println!();
+    println!(
+        "{}",
+        render_table(
+            &headers.iter().map(String::as_str).collect::<Vec<_>>(),
+            &measurements
+        )
+        .join("\n")
+    );
+    println!();
+
+    for (i, part) in parts.iter().enumerate() {
+        let points: Vec<(usize, Duration)> = measurements
+            .iter()
+            .filter_map(|m| Some((m.size, m.parts[i]?)))
+            .collect();
+
+        match estimate_exponent(&points) {
+            Some(exponent) => println!(
+                "Part {}: time grows with {ANSI_BOLD}n^{exponent:.2}{ANSI_RESET} ({})",
+                part.part,
+                describe_exponent(exponent)
+            ),
+            None => println!("Part {}: not enough measurements.", part.part),
+        }
+    }
+}
+
+/// Returns the median duration of running `f` repeatedly for [`SAMPLE_BUDGET`].
+fn measure<T>(f: impl Fn() -> T) -> Duration {
+    let mut samples: Vec<Duration> = vec![];
+    let started = Instant::now();
+
+    while samples.len() < MAX_SAMPLES && (samples.is_empty() || started.elapsed() < SAMPLE_BUDGET) {
+        let timer = Instant::now();
+        black_box(f());
+        samples.push(timer.elapsed());
+    }
+
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+fn render_table(headers: &[&str], measurements: &[Measurement]) -> Vec<String> {
+    let rows: Vec<Vec<String>> = measurements
+        .iter()
+        .map(|m| {
+            [m.size.to_string(), format_bytes(m.input_bytes as u64)]
+                .into_iter()
+                .chain(
+                    m.parts
+                        .iter()
+                        .map(|d| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}"))),
+                )
+                .collect()
+        })
+        .collect();
+
+    markdown_table(headers, &rows)
+}
+
+/// Fits `time = c * n^k` to the measurements and returns `k`.
+/// Only the largest sizes are used, as constant overhead dominates small inputs.
+fn estimate_exponent(points: &[(usize, Duration)]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = points
+        .iter()
+        .rev()
+        .take(4)
+        .map(|(size, duration)| ((*size as f64).ln(), duration.as_secs_f64().max(1e-9).ln()))
+        .collect();
+
+    if points.len() < 3 {
+        return None;
+    }
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    Some(covariance / variance)
+}
+
+fn describe_exponent(exponent: f64) -> String {
+    let nearest = exponent.round();
+
+    let name = match nearest as i64 {
+        _ if (exponent - nearest).abs() > 0.25 => None,
+        i64::MIN..=0 => Some("constant"),
+        1 => Some("linear"),
+        2 => Some("quadratic"),
+        3 => Some("cubic"),
+        _ => None,
+    };
+
+    match name {
+        Some(name) => format!("≈ {name} in n"),
+        None => format!("≈ O(n^{exponent:.1})"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_exponent, estimate_exponent, render_table, Measurement};
+    use std::time::Duration;
+
+    #[test]
+    fn estimates_exponent() {
+        let quadratic: Vec<(usize, Duration)> = [8, 16, 32, 64, 128]
+            .into_iter()
+            .map(|n| (n, Duration::from_nanos((n * n) as u64 * 100)))
+            .collect();
+
+        let exponent = estimate_exponent(&quadratic).unwrap();
+        assert!((exponent - 2.0).abs() < 1e-6);
+
+        assert_eq!(estimate_exponent(&quadratic[..2]), None);
+    }
+
+    #[test]
+    fn describes_exponents() {
+        assert_eq!(describe_exponent(1.08), "≈ linear in n");
+        assert_eq!(describe_exponent(1.96), "≈ quadratic in n");
+        assert_eq!(describe_exponent(1.5), "≈ O(n^1.5)");
+        assert_eq!(describe_exponent(-0.1), "≈ constant in n");
+    }
+
+    #[test]
+    fn renders_table() {
+        let table = render_table(
+            &["n", "Input", "Part 1", "Part 2"],
+            &[Measurement {
+                size: 8,
+                input_bytes: 2048,
+                parts: vec![Some(Duration::from_micros(12)), None],
+            }],
+        );
+
+        assert_eq!(table[0], "| n |  Input  | Part 1 | Part 2 |");
+        assert_eq!(table[2], "| 8 | 2.0 KiB | 12.0µs |   -    |");
+    }
+}
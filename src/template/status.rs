@@ -0,0 +1,392 @@
+/// Module that combines the local sources of information about a season into an overview:
+/// solution files, inputs, examples, stored timings, recorded submissions and the readme.
+use std::{collections::HashMap, fs, path::Path, time::SystemTime};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days, readme_benchmarks, submissions::Submissions, timings::Timings, Day, ANSI_BOLD,
+    ANSI_RESET,
+};
+
+/// Whether a day has stored timings and if they are older than its solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingStatus {
+    Missing,
+    Current,
+    Stale,
+}
+
+/// The files on disk that belong to a day.
+#[derive(Clone, Debug, Default)]
+pub struct DayFiles {
+    pub bin_modified: Option<SystemTime>,
+    pub has_input: bool,
+    pub has_examples: bool,
+}
+
+impl DayFiles {
+    pub fn read(day: Day) -> Self {
+        let bin_modified = fs::metadata(format!("src/bin/{day}.rs"))
+            .and_then(|m| m.modified())
+            .ok();
+
+        let has_examples = fs::read_dir("data/examples").is_ok_and(|entries| {
+            entries.filter_map(Result::ok).any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let is_day_file =
+                    name == format!("{day}.txt") || name.starts_with(&format!("{day}-"));
+                is_day_file && is_non_empty(&entry.path())
+            })
+        });
+
+        Self {
+            bin_modified,
+            has_input: is_non_empty(Path::new(&format!("data/inputs/{day}.txt"))),
+            has_examples,
+        }
+    }
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Sources of information that are shared by all days.
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    pub timings: Timings,
+    pub timings_modified: Option<SystemTime>,
+    pub submissions: Submissions,
+    pub readme_days: Vec<Day>,
+}
+
+impl Sources {
+    pub fn read() -> Self {
+        Self {
+            timings: Timings::read_from_file(),
+            timings_modified: Timings::file_modified(),
+            submissions: Submissions::read_from_file(),
+            readme_days: readme_benchmarks::read_listed_days().unwrap_or_default(),
+        }
+    }
+}
+
+/// The status of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub has_input: bool,
+    pub has_examples: bool,
+    pub solved: [bool; 2],
+    pub timing: TimingStatus,
+    pub in_readme: bool,
+}
+
+impl DayStatus {
+    pub fn new(day: Day, files: &DayFiles, sources: &Sources) -> Self {
+        let timing = sources
+            .timings
+            .data
+            .iter()
+            .find(|t| t.day == day && (t.part_1.is_some() || t.part_2.is_some()));
+
+        // Timings stored by older versions lack `measured_at`, fall back to when the file was written.
+        let measured_at = timing.and_then(|t| t.measured_at.or(sources.timings_modified));
+
+        let timing = match (timing, files.bin_modified, measured_at) {
+            (None, _, _) => TimingStatus::Missing,
+            (Some(_), Some(bin), Some(measured)) if bin > measured => TimingStatus::Stale,
+            (Some(_), _, _) => TimingStatus::Current,
+        };
+
+        Self {
+            day,
+            scaffolded: files.bin_modified.is_some(),
+            has_input: files.has_input,
+            has_examples: files.has_examples,
+            solved: [
+                sources.submissions.is_solved(day, 1),
+                sources.submissions.is_solved(day, 2),
+            ],
+            timing,
+            in_readme: sources.readme_days.contains(&day),
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.solved.iter().filter(|&&solved| solved).count()
+    }
+
+    fn flags(&self) -> String {
+        let flag = |set: bool, c: char| if set { c } else { '-' };
+
+        [
+            flag(self.scaffolded, 'S'),
+            flag(self.has_input, 'I'),
+            flag(self.has_examples, 'E'),
+            match self.timing {
+                TimingStatus::Missing => '-',
+                TimingStatus::Current => 'T',
+                TimingStatus::Stale => '!',
+            },
+            flag(self.in_readme, 'R'),
+        ]
+        .iter()
+        .collect()
+    }
+}
+
+/// Collects the status of every day of advent.
+pub fn collect() -> Vec<DayStatus> {
+    let sources = Sources::read();
+    all_days()
+        .map(|day| DayStatus::new(day, &DayFiles::read(day), &sources))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the statuses as a calendar with five days per row.
+pub fn render_grid(statuses: &[DayStatus]) -> String {
+    let mut lines: Vec<String> = statuses
+        .chunks(5)
+        .map(|week| {
+            week.iter()
+                .map(|status| {
+                    let stars: String = status
+                        .solved
+                        .iter()
+                        .map(|&solved| if solved { '★' } else { '·' })
+                        .collect();
+
+                    if status.scaffolded {
+                        format!(
+                            "{ANSI_BOLD}{}{ANSI_RESET} {stars} {}",
+                            status.day,
+                            status.flags()
+                        )
+                    } else {
+                        format!("{} {stars} {}", status.day, status.flags())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("   ")
+        })
+        .collect();
+
+    let stars: usize = statuses.iter().map(DayStatus::stars).sum();
+    let scaffolded = statuses.iter().filter(|s| s.scaffolded).count();
+    let benched = statuses
+        .iter()
+        .filter(|s| s.timing != TimingStatus::Missing)
+        .count();
+    let stale = statuses
+        .iter()
+        .filter(|s| s.timing == TimingStatus::Stale)
+        .count();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {stars}/{} · {ANSI_BOLD}Scaffolded:{ANSI_RESET} {scaffolded} · {ANSI_BOLD}Benchmarked:{ANSI_RESET} {benched} ({stale} stale)",
+        statuses.len() * 2
+    ));
+    lines.push(
+        "★ solved part · S scaffolded · I input · E examples · T timed (! stale) · R in readme"
+            .into(),
+    );
+
+    lines.join("\n")
+}
+
+pub fn to_json(statuses: &[DayStatus]) -> JsonValue {
+    let days = statuses
+        .iter()
+        .map(|status| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+            map.insert("day".into(), JsonValue::String(status.day.to_string()));
+            map.insert("scaffolded".into(), JsonValue::Boolean(status.scaffolded));
+            map.insert("input".into(), JsonValue::Boolean(status.has_input));
+            map.insert("examples".into(), JsonValue::Boolean(status.has_examples));
+            map.insert("part_1".into(), JsonValue::Boolean(status.solved[0]));
+            map.insert("part_2".into(), JsonValue::Boolean(status.solved[1]));
+            map.insert(
+                "timing".into(),
+                JsonValue::String(
+                    match status.timing {
+                        TimingStatus::Missing => "missing",
+                        TimingStatus::Current => "current",
+                        TimingStatus::Stale => "stale",
+                    }
+                    .into(),
+                ),
+            );
+            map.insert("readme".into(), JsonValue::Boolean(status.in_readme));
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    #[allow(clippy::cast_precision_loss)]
+    let stars = statuses.iter().map(DayStatus::stars).sum::<usize>() as f64;
+    map.insert("stars".into(), JsonValue::Number(stars));
+    map.insert("days".into(), JsonValue::Array(days));
+
+    JsonValue::Object(map)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_grid, to_json, DayFiles, DayStatus, Sources, TimingStatus};
+    use crate::{
+        day,
+        template::{
+            all_days,
+            submissions::{Submission, Submissions, Verdict},
+            timings::{Timing, Timings},
+        },
+    };
+    use std::{
+        collections::HashMap,
+        time::{Duration, SystemTime},
+    };
+    use tinyjson::JsonValue;
+
+    fn get_mock_sources(measured_at: SystemTime) -> Sources {
+        Sources {
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(20).into()),
+                    measured_at: Some(measured_at),
+                    environment: None,
+                }],
+            },
+            timings_modified: Some(measured_at),
+            submissions: Submissions {
+                data: vec![
+                    Submission {
+                        day: day!(1),
+                        part: 1,
+                        answer: "1".into(),
+                        verdict: Verdict::Correct,
+                    },
+                    Submission {
+                        day: day!(1),
+                        part: 2,
+                        answer: "2".into(),
+                        verdict: Verdict::Incorrect,
+                    },
+                ],
+            },
+            readme_days: vec![day!(1)],
+        }
+    }
+
+    #[test]
+    fn combines_sources() {
+        let now = SystemTime::now();
+        let files = DayFiles {
+            bin_modified: Some(now - Duration::from_secs(60)),
+            has_input: true,
+            has_examples: false,
+        };
+
+        let status = DayStatus::new(day!(1), &files, &get_mock_sources(now));
+
+        assert_eq!(
+            status,
+            DayStatus {
+                day: day!(1),
+                scaffolded: true,
+                has_input: true,
+                has_examples: false,
+                solved: [true, false],
+                timing: TimingStatus::Current,
+                in_readme: true,
+            }
+        );
+    }
+
+    #[test]
+    fn detects_stale_timings() {
+        let now = SystemTime::now();
+        let files = DayFiles {
+            bin_modified: Some(now),
+            ..DayFiles::default()
+        };
+
+        let sources = get_mock_sources(now - Duration::from_secs(60));
+        assert_eq!(
+            DayStatus::new(day!(1), &files, &sources).timing,
+            TimingStatus::Stale
+        );
+        assert_eq!(
+            DayStatus::new(day!(2), &files, &sources).timing,
+            TimingStatus::Missing
+        );
+    }
+
+    #[test]
+    fn detects_stale_timings_per_day() {
+        let now = SystemTime::now();
+        let files = DayFiles {
+            bin_modified: Some(now),
+            ..DayFiles::default()
+        };
+
+        // Benching another day later rewrites the file, but does not refresh the timing of day 1.
+        let mut sources = get_mock_sources(now - Duration::from_secs(60));
+        sources.timings_modified = Some(now + Duration::from_secs(60));
+        assert_eq!(
+            DayStatus::new(day!(1), &files, &sources).timing,
+            TimingStatus::Stale
+        );
+
+        sources.timings.data[0].measured_at = None;
+        assert_eq!(
+            DayStatus::new(day!(1), &files, &sources).timing,
+            TimingStatus::Current
+        );
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let sources = get_mock_sources(SystemTime::now());
+        let statuses: Vec<_> = all_days()
+            .map(|day| DayStatus::new(day, &DayFiles::default(), &sources))
+            .collect();
+
+        let grid = render_grid(&statuses);
+        let lines: Vec<_> = grid.lines().collect();
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with("01 ★· ---TR   02 ··"));
+        assert!(lines[4].ends_with("25 ·· -----"));
+        assert!(lines[6].contains("1/50"));
+    }
+
+    #[test]
+    fn serializes_statuses() {
+        let sources = get_mock_sources(SystemTime::now());
+        let statuses = vec![DayStatus::new(day!(1), &DayFiles::default(), &sources)];
+
+        let json = to_json(&statuses);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map.get("stars").unwrap().get::<f64>(), Some(&1.0));
+        assert_eq!(
+            map.get("days")
+                .unwrap()
+                .get::<Vec<JsonValue>>()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,261 @@
+/// Module that keeps a local record of answers submitted via aoc-cli.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The response of the aoc server to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// The response could not be interpreted, e.g. because of rate limiting or an already solved part.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interprets the output of `aoc submit`.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// All answers submitted so far, in the order they were submitted.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns empty submissions.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Appends a submission to the file on disk.
+    pub fn record(submission: Submission) -> Result<(), Error> {
+        let mut submissions = Self::read_from_file();
+        submissions.data.push(submission);
+        submissions.store_file()
+    }
+
+    /// Whether a correct answer was submitted for a part.
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.data
+            .iter()
+            .any(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+    }
+
+    /// The answer that was accepted for a part, if any.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submission, Submissions, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn interprets_responses() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = Submissions {
+            data: vec![
+                Submission {
+                    day: day!(3),
+                    part: 1,
+                    answer: "42".into(),
+                    verdict: Verdict::Incorrect,
+                },
+                Submission {
+                    day: day!(3),
+                    part: 1,
+                    answer: "43".into(),
+                    verdict: Verdict::Correct,
+                },
+            ],
+        };
+
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+
+        assert_eq!(parsed.data, submissions.data);
+        assert!(parsed.is_solved(day!(3), 1));
+        assert!(!parsed.is_solved(day!(3), 2));
+        assert_eq!(parsed.correct_answer(day!(3), 1), Some("43"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_part() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct" }] }"#;
+        Submissions::try_from(json.to_string()).unwrap();
+    }
+}
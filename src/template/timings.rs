@@ -1,17 +1,141 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{environment::Environment, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON schema that is written by [`Timings::store_file`].
+/// Version 1 stored pre-formatted duration strings and is migrated on read.
+pub const SCHEMA_VERSION: u8 = 2;
+
+/// Distribution of the samples of a benchmarked part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u64,
+    pub median_nanos: u64,
+    pub min_nanos: u64,
+    pub max_nanos: u64,
+}
+
+impl Stats {
+    /// Computes stats from the durations of all samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut nanos: Vec<u64> = samples.iter().map(nanos_of).collect();
+        nanos.sort_unstable();
+
+        Some(Self {
+            min_nanos: *nanos.first()?,
+            max_nanos: *nanos.last()?,
+            median_nanos: nanos[nanos.len() / 2],
+            samples: nanos.len() as u64,
+        })
+    }
+}
+
+/// Heap usage of a single run of a part, measured by the counting allocator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Memory {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub peak_bytes: u64,
+}
+
+impl Memory {
+    /// Formats the memory usage for display, e.g. `3 allocs, 1.2 KiB, peak 1.0 KiB`.
+    pub fn format(&self) -> String {
+        format!(
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Benchmark time of a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartTiming {
+    /// Mean duration of a run.
+    pub nanos: u64,
+    /// Not available for timings migrated from version 1.
+    pub stats: Option<Stats>,
+    /// Only available when measured with the `count-allocations` feature.
+    pub memory: Option<Memory>,
+}
+
+impl PartTiming {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos)
+    }
+
+    /// Formats the mean duration for display, e.g. `74.1ns`.
+    pub fn format(&self) -> String {
+        format!("{:.1?}", self.duration())
+    }
+}
+
+impl From<Duration> for PartTiming {
+    fn from(value: Duration) -> Self {
+        Self {
+            nanos: nanos_of(&value),
+            stats: None,
+            memory: None,
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn nanos_of(duration: &Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// When the day was benchmarked. Not available for timings stored by older versions.
+    pub measured_at: Option<SystemTime>,
+    /// Where the day was benchmarked. Not available for timings stored by older versions.
+    pub environment: Option<Environment>,
+}
+
+impl Timing {
+    pub fn total_nanos(&self) -> u64 {
+        [self.part_1, self.part_2]
+            .iter()
+            .flatten()
+            .map(|p| p.nanos)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -37,6 +161,13 @@ impl Timings {
             .unwrap_or_default()
     }
 
+    /// Last modification time of the timings file, if it exists.
+    pub fn file_modified() -> Option<SystemTime> {
+        fs::metadata(TIMINGS_FILE_PATH)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -51,13 +182,30 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// The environment that all days were measured in, from the latest run.
+    /// `None` if a day lacks an environment or days were measured with different builds.
+    pub fn environment(&self) -> Option<&Environment> {
+        let mut environments = self.data.iter().map(|t| t.environment.as_ref());
+        let first = environments.next()??;
+
+        environments.try_fold(first, |latest, environment| {
+            let environment = environment.filter(|e| e.is_same_build(latest))?;
+            Some(if environment.timestamp > latest.timestamp {
+                environment
+            } else {
+                latest
+            })
+        })
+    }
+
     /// Sum up total duration of timings as millis.
+    #[allow(clippy::cast_precision_loss)]
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<u64>() as f64 / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -73,6 +221,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -87,20 +239,56 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
-        let json_data = json
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // version 1 did not store a version.
+        let version = match json.get("version") {
+            None => 1.0,
+            Some(version) => *version
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        let is_v1 = version == 1.0;
+        if !is_v1 && version != f64::from(SCHEMA_VERSION) {
+            return Err(format!("unsupported timings version `{version}`."));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // older versions stored a single environment for all days.
+        let environment = match json.get("environment") {
+            None | Some(JsonValue::Null) => None,
+            Some(environment) => Some(Environment::try_from(environment)?),
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
+                .map(|timing| {
+                    let mut timing = if is_v1 {
+                        Timing::try_from_v1(timing)?
+                    } else {
+                        Timing::try_from(timing)?
+                    };
+                    timing.environment = timing.environment.or_else(|| environment.clone());
+                    Ok(timing)
+                })
+                .collect::<Result<_, String>>()?,
         })
     }
 }
@@ -112,26 +300,28 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let part_to_json = |part: Option<PartTiming>| match part {
+            Some(part) => JsonValue::from(&part),
+            None => JsonValue::Null,
+        };
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        map.insert("part_1".into(), part_to_json(value.part_1));
+        map.insert("part_2".into(), part_to_json(value.part_2));
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        if let Some(since_epoch) = value
+            .measured_at
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        {
+            map.insert(
+                "measured_at".into(),
+                JsonValue::Number(since_epoch.as_secs_f64()),
+            );
+        }
+
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
 
         JsonValue::Object(map)
     }
@@ -145,32 +335,178 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
+        let parse_part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(part) => PartTiming::try_from(part).map(Some),
+            None => Err(format!("Expected timing.{key} to be null or an object.")),
+        };
+
+        let measured_at = match json.get("measured_at") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(secs)) => Some(
+                Duration::try_from_secs_f64(*secs)
+                    .ok()
+                    .and_then(|since_epoch| UNIX_EPOCH.checked_add(since_epoch))
+                    .ok_or("Expected timing.measured_at to be a number.")?,
+            ),
+            Some(_) => return Err("Expected timing.measured_at to be a number.".into()),
+        };
+
+        let environment = match json.get("environment") {
+            None | Some(JsonValue::Null) => None,
+            Some(environment) => Some(Environment::try_from(environment)?),
+        };
+
+        Ok(Timing {
+            day: parse_day(json)?,
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
+            measured_at,
+            environment,
+        })
+    }
+}
+
+impl Timing {
+    /// Reads a timing of schema version 1, which stored parts as formatted durations like `"74.13ns"`.
+    fn try_from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let parse_part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => parse_formatted_duration(s)
+                .map(|nanos| Some(Duration::from_nanos(nanos).into()))
+                .ok_or(format!("Could not parse timing.{key} `{s}` as a duration.")),
+            _ => Err(format!("Expected timing.{key} to be null or string.")),
+        };
+
+        json.get("total_nanos")
+            .and_then(|v| v.get::<f64>())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            day: parse_day(json)?,
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
+            measured_at: None,
+            environment: None,
+        })
+    }
+}
+
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+/// Parses the `Debug` output of a [`Duration`] back into nanoseconds.
+/// For possible formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+pub(crate) fn parse_formatted_duration(s: &str) -> Option<u64> {
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.parse::<f64>().ok();
+
+    let nanos = parse("ns")
+        .or_else(|| parse("µs").map(|x| x * 1_000_f64))
+        .or_else(|| parse("ms").map(|x| x * 1_000_000_f64))
+        .or_else(|| parse("s").map(|x| x * 1_000_000_000_f64))?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+
+        if let Some(stats) = value.stats {
+            map.insert("samples".into(), JsonValue::Number(stats.samples as f64));
+            map.insert(
+                "median_nanos".into(),
+                JsonValue::Number(stats.median_nanos as f64),
+            );
+            map.insert(
+                "min_nanos".into(),
+                JsonValue::Number(stats.min_nanos as f64),
+            );
+            map.insert(
+                "max_nanos".into(),
+                JsonValue::Number(stats.max_nanos as f64),
+            );
+        }
+
+        if let Some(memory) = value.memory {
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(memory.allocations as f64),
+            );
+            map.insert(
+                "allocated_bytes".into(),
+                JsonValue::Number(memory.allocated_bytes as f64),
+            );
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(memory.peak_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .filter(|n| **n >= 0.0)
+                .map(|n| *n as u64)
+        };
+
+        let nanos = number("nanos").ok_or("Expected part timing.nanos to be a number.")?;
+
+        let stats = match number("samples") {
+            None => None,
+            Some(samples) => Some(Stats {
+                samples,
+                median_nanos: number("median_nanos")
+                    .ok_or("Expected part timing.median_nanos to be a number.")?,
+                min_nanos: number("min_nanos")
+                    .ok_or("Expected part timing.min_nanos to be a number.")?,
+                max_nanos: number("max_nanos")
+                    .ok_or("Expected part timing.max_nanos to be a number.")?,
+            }),
+        };
+
+        let memory = match number("allocations") {
+            None => None,
+            Some(allocations) => Some(Memory {
+                allocations,
+                allocated_bytes: number("allocated_bytes")
+                    .ok_or("Expected part timing.allocated_bytes to be a number.")?,
+                peak_bytes: number("peak_bytes")
+                    .ok_or("Expected part timing.peak_bytes to be a number.")?,
+            }),
+        };
+
+        Ok(PartTiming {
+            nanos,
+            stats,
+            memory,
         })
     }
 }
@@ -180,6 +516,7 @@ impl TryFrom<&JsonValue> for Timing {
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
+    use std::time::Duration;
 
     use super::{Timing, Timings};
 
@@ -188,39 +525,110 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(Duration::from_millis(10).into()),
+                    part_2: Some(Duration::from_millis(20).into()),
+                    measured_at: None,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(Duration::from_millis(30).into()),
+                    part_2: Some(Duration::from_millis(40).into()),
+                    measured_at: None,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(Duration::from_millis(40).into()),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    measured_at: None,
+                    environment: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartTiming, Stats, Timings},
+        };
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "median_nanos": 900000, "min_nanos": 800000, "max_nanos": 2000000 }, "part_2": { "nanos": 5 } }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000,
+                    stats: Some(Stats {
+                        samples: 10,
+                        median_nanos: 900_000,
+                        min_nanos: 800_000,
+                        max_nanos: 2_000_000,
+                    }),
+                    memory: None,
+                })
+            );
+            assert_eq!(timing.part_2, Some(Duration::from_nanos(5).into()));
+            assert_eq!(timing.total_nanos(), 1_000_005);
+        }
+
+        #[test]
+        fn handles_environment() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 5 }, "part_2": null, "environment": { "cpu": null, "cores": 4, "rustc": "1.83.0", "target": null, "profile": "release", "opt_level": "3", "commit": "1a2b3c4", "timestamp": 0 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let environment = timings.data[0].environment.as_ref().unwrap();
+            assert_eq!(environment.cores, Some(4));
+            assert_eq!(environment.cpu, None);
+            assert_eq!(environment.commit.as_deref(), Some("1a2b3c4"));
+            assert_eq!(timings.environment(), Some(environment));
+
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 5 }, "part_2": null }] }"#.to_string();
+            assert_eq!(Timings::try_from(json).unwrap().data[0].environment, None);
+        }
+
+        #[test]
+        fn migrates_shared_environment() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 5 }, "part_2": null }, { "day": "02", "part_1": { "nanos": 5 }, "part_2": null, "environment": { "cores": 8, "profile": "release", "opt_level": "3", "timestamp": 0 } }], "environment": { "cores": 4, "profile": "release", "opt_level": "3", "timestamp": 0 } }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].environment.as_ref().unwrap().cores, Some(4));
+            assert_eq!(timings.data[1].environment.as_ref().unwrap().cores, Some(8));
+            assert_eq!(timings.environment(), None);
+        }
+
+        #[test]
+        fn rejects_invalid_measured_at() {
+            for secs in ["-1", "1e300", "\"yesterday\""] {
+                let json = format!(r#"{{ "version": 2, "data": [{{ "day": "01", "part_1": null, "part_2": null, "measured_at": {secs} }}] }}"#);
+                assert_eq!(
+                    Timings::try_from(json).err().as_deref(),
+                    Some("Expected timing.measured_at to be a number.")
+                );
+            }
+        }
+
+        #[test]
+        fn migrates_version_1() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "83.7µs", "part_2": null, "total_nanos": 83700 }, { "day": "02", "part_1": "1.2ms", "part_2": "481.0ns", "total_nanos": 1200481 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1.unwrap().nanos, 83_700);
+            assert_eq!(timings.data[0].part_1.unwrap().format(), "83.7µs");
+            assert_eq!(timings.data[0].part_2, None);
+            assert_eq!(timings.data[1].part_1.unwrap().format(), "1.2ms");
+            assert_eq!(timings.data[1].total_nanos(), 1_200_481);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_version() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
         }
 
         #[test]
@@ -247,9 +655,71 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
-        use std::collections::HashMap;
+        use crate::template::timings::{
+            format_bytes, Memory, PartTiming, Stats, Timings, SCHEMA_VERSION,
+        };
+        use std::{
+            collections::HashMap,
+            time::{Duration, UNIX_EPOCH},
+        };
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1 = Some(PartTiming {
+                nanos: 12,
+                stats: Stats::from_samples(&[
+                    Duration::from_nanos(20),
+                    Duration::from_nanos(10),
+                    Duration::from_nanos(11),
+                ]),
+                memory: Some(Memory {
+                    allocations: 3,
+                    allocated_bytes: 2048,
+                    peak_bytes: 1536,
+                }),
+            });
+            timings.data[1].measured_at = Some(UNIX_EPOCH + Duration::from_secs(1_733_011_200));
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(
+                parsed.data[0].part_1.unwrap().stats,
+                Some(Stats {
+                    samples: 3,
+                    median_nanos: 11,
+                    min_nanos: 10,
+                    max_nanos: 20,
+                })
+            );
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.data[0].total_nanos(), 20_000_012);
+            assert_eq!(parsed.data[0].measured_at, None);
+            assert_eq!(parsed.data[1].measured_at, timings.data[1].measured_at);
+        }
+
+        #[test]
+        fn formats_memory() {
+            let memory = Memory {
+                allocations: 3,
+                allocated_bytes: 1536,
+                peak_bytes: 512,
+            };
+
+            assert_eq!(memory.format(), "3 allocs, 1.5 KiB, peak 512 B");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+
+        #[test]
+        fn stores_schema_version() {
+            let value = JsonValue::from(get_mock_timings());
+            let version = value.get::<HashMap<String, JsonValue>>().unwrap()["version"].clone();
+            assert_eq!(version.get::<f64>(), Some(&f64::from(SCHEMA_VERSION)));
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -273,19 +743,21 @@ mod tests {
             day,
             template::timings::{Timing, Timings},
         };
+        use std::time::Duration;
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1: Some(Duration::from_millis(1).into()),
+                    part_2: Some(Duration::from_millis(2).into()),
+                    measured_at: None,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +765,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(Duration::from_millis(1).into()),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    measured_at: None,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,18 +782,22 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
+                    measured_at: None,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                environment::Environment,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -333,7 +810,8 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    measured_at: None,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +831,8 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    measured_at: None,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,10 +840,41 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_environment_per_day() {
+            let environment = |cores| Environment {
+                cores: Some(cores),
+                profile: "release".into(),
+                opt_level: "3".into(),
+                ..Environment::default()
+            };
+
+            let mut timings = get_mock_timings();
+            for timing in &mut timings.data {
+                timing.environment = Some(environment(16));
+            }
+            assert_eq!(timings.environment(), Some(&environment(16)));
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: None,
+                    part_2: None,
+                    measured_at: None,
+                    environment: Some(environment(4)),
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[0].environment, Some(environment(16)));
+            assert_eq!(merged.data[1].environment, Some(environment(4)));
+            assert_eq!(merged.environment(), None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
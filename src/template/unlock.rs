@@ -0,0 +1,231 @@
+/// Module that waits for the next puzzle to unlock.
+/// All time-related calls go through the [`Clock`] trait so that the logic can be tested without waiting.
+use std::{fmt::Display, thread, time::Duration};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::template::{all_days, Day};
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// A [`Clock`] backed by the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Returns the year whose puzzles are solved: the configured `AOC_YEAR` if set, otherwise the year of `now`.
+/// Downloads use the same year, see [`get_year`](crate::template::aoc_cli::get_year).
+pub fn puzzle_year(now: DateTime<Utc>, configured: Option<u16>) -> i32 {
+    configured.map_or(now.year(), i32::from)
+}
+
+/// Returns the next day of `year` that unlocks strictly after `now`, together with its unlock time.
+/// Returns `None` if all puzzles of `year` have unlocked already.
+pub fn next_unlock(now: DateTime<Utc>, year: i32) -> Option<(Day, DateTime<Utc>)> {
+    all_days()
+        .filter_map(|day| Some((day, day.unlock_time(year)?)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+/// Blocks until `unlock` is reached, calling `on_tick` with the remaining time roughly once per second.
+pub fn wait_until(clock: &impl Clock, unlock: DateTime<Utc>, mut on_tick: impl FnMut(Duration)) {
+    loop {
+        let Ok(remaining) = (unlock - clock.now()).to_std() else {
+            return;
+        };
+
+        if remaining.is_zero() {
+            return;
+        }
+
+        on_tick(remaining);
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+}
+
+/// Calls `f` until it succeeds or `attempts` are exhausted, doubling the delay between attempts.
+pub fn retry_with_backoff<T, E: Display>(
+    clock: &impl Clock,
+    attempts: u32,
+    initial_delay: Duration,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut delay = initial_delay;
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                eprintln!("Attempt {attempt}/{attempts} failed: {e}. Retrying in {delay:?}...");
+                clock.sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Formats a duration as a `HH:MM:SS` countdown, prefixed with the number of days if necessary.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        format_countdown, next_unlock, puzzle_year, retry_with_backoff, wait_until, Clock,
+    };
+    use crate::day;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::{cell::RefCell, time::Duration};
+
+    struct MockClock {
+        now: RefCell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl MockClock {
+        fn new(now: DateTime<Utc>) -> Self {
+            Self {
+                now: RefCell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += chrono::Duration::from_std(duration).unwrap();
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn finds_first_day_before_december() {
+        let (day, unlock) = next_unlock(utc(11, 30, 12, 0), 2024).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(unlock, utc(12, 1, 5, 0));
+    }
+
+    #[test]
+    fn finds_next_day_during_advent() {
+        assert_eq!(next_unlock(utc(12, 5, 4, 59), 2024).unwrap().0, day!(5));
+        assert_eq!(next_unlock(utc(12, 5, 5, 0), 2024).unwrap().0, day!(6));
+        assert_eq!(next_unlock(utc(12, 5, 23, 0), 2024).unwrap().0, day!(6));
+    }
+
+    #[test]
+    fn finds_nothing_after_advent() {
+        assert_eq!(next_unlock(utc(12, 25, 6, 0), 2024), None);
+    }
+
+    #[test]
+    fn uses_configured_year() {
+        let now = Utc.with_ymd_and_hms(2025, 11, 20, 0, 0, 0).unwrap();
+
+        assert_eq!(puzzle_year(now, Some(2024)), 2024);
+        assert_eq!(puzzle_year(now, None), 2025);
+
+        assert_eq!(next_unlock(now, puzzle_year(now, Some(2024))), None);
+
+        let (day, unlock) = next_unlock(now, puzzle_year(now, None)).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2025, 12, 1, 5, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = MockClock::new(utc(12, 1, 4, 59));
+        let mut ticks = vec![];
+
+        wait_until(&clock, utc(12, 1, 5, 0), |remaining| ticks.push(remaining));
+
+        assert_eq!(clock.now(), utc(12, 1, 5, 0));
+        assert_eq!(ticks.len(), 60);
+        assert_eq!(ticks[0], Duration::from_secs(60));
+    }
+
+    #[test]
+    fn does_not_wait_for_past_unlock() {
+        let clock = MockClock::new(utc(12, 1, 6, 0));
+        wait_until(&clock, utc(12, 1, 5, 0), |_| panic!("should not tick"));
+        assert!(clock.sleeps.borrow().is_empty());
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = MockClock::new(utc(12, 1, 5, 0));
+        let mut calls = 0;
+
+        let result = retry_with_backoff(&clock, 5, Duration::from_secs(1), || {
+            calls += 1;
+            if calls < 3 {
+                Err("not yet")
+            } else {
+                Ok(calls)
+            }
+        });
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![Duration::from_secs(1), Duration::from_secs(2)]
+        );
+    }
+
+    #[test]
+    fn gives_up_after_attempts() {
+        let clock = MockClock::new(utc(12, 1, 5, 0));
+        let result: Result<(), _> =
+            retry_with_backoff(&clock, 3, Duration::from_secs(1), || Err("down"));
+
+        assert_eq!(result, Err("down"));
+        assert_eq!(clock.sleeps.borrow().len(), 2);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 3600 + 61)),
+            "03:01:01"
+        );
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+}
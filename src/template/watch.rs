@@ -0,0 +1,358 @@
+/// Module that re-runs a solution whenever one of its sources or data files changes.
+/// Changes are detected by polling modification times, so no external file watcher is needed.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_multi::child_commands::parse_panic_message;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of all files that are relevant for a day.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(HashMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Collects the modification times of `src/bin/<day>.rs`, everything below `src/utils`
+    /// and the day's input and example files.
+    pub fn take(day: Day) -> Self {
+        let mut paths = vec![PathBuf::from(format!("src/bin/{day}.rs"))];
+        collect_files(Path::new("src/utils"), &mut paths);
+        collect_day_files(Path::new("data/inputs"), day, &mut paths);
+        collect_day_files(Path::new("data/examples"), day, &mut paths);
+
+        Self(
+            paths
+                .into_iter()
+                .filter_map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                    Some((path, modified))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the paths that were added, removed or modified between `self` and `newer`.
+    pub fn changes(&self, newer: &Self) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = newer
+            .0
+            .iter()
+            .filter(|(path, modified)| self.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .chain(
+                self.0
+                    .keys()
+                    .filter(|path| !newer.0.contains_key(*path))
+                    .cloned(),
+            )
+            .collect();
+
+        changed.sort_unstable();
+        changed
+    }
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            collect_files(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+/// Collects files such as `05.txt` or `05-2.txt` that belong to a day.
+fn collect_day_files(dir: &Path, day: Day, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let prefix = day.to_string();
+
+    paths.extend(
+        entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem == prefix || stem.starts_with(&format!("{prefix}-")))
+            }),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of running the example tests of a day.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: Vec<String>,
+}
+
+/// Result of a single watch cycle.
+#[derive(Debug)]
+pub enum Cycle {
+    BuildFailed(String),
+    Finished {
+        tests: TestSummary,
+        answers: Vec<String>,
+        run_error: Option<String>,
+    },
+}
+
+/// Watches the files of `day` and re-runs build, examples and real input on every change.
+pub fn watch(day: Day, is_release: bool) -> ! {
+    let mut snapshot = Snapshot::take(day);
+    let mut changed = vec![];
+    let mut iteration = 1;
+
+    loop {
+        print!("{CLEAR_SCREEN}");
+        print_header(day, iteration, &changed);
+        println!("{ANSI_ITALIC}building & running...{ANSI_RESET}");
+
+        let cycle = run_cycle(day, is_release);
+
+        print!("{CLEAR_SCREEN}");
+        print_header(day, iteration, &changed);
+        print_cycle(&cycle);
+        println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = Snapshot::take(day);
+            changed = snapshot.changes(&next);
+            if !changed.is_empty() {
+                snapshot = next;
+                break;
+            }
+        }
+
+        iteration += 1;
+    }
+}
+
+fn run_cycle(day: Day, is_release: bool) -> Cycle {
+    let build = match cargo("build", day, is_release) {
+        Ok(output) => output,
+        Err(e) => return Cycle::BuildFailed(e.to_string()),
+    };
+
+    if !build.status.success() {
+        return Cycle::BuildFailed(String::from_utf8_lossy(&build.stderr).into_owned());
+    }
+
+    let tests = cargo("test", day, is_release)
+        .map(|output| parse_test_output(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+
+    let (answers, run_error) = match cargo("run", day, is_release) {
+        Ok(output) => {
+            let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+            let error = (!output.status.success()).then(|| {
+                parse_panic_message(&String::from_utf8_lossy(&output.stderr))
+                    .unwrap_or_else(|| "exited with an error.".into())
+            });
+            (answers, error)
+        }
+        Err(e) => (vec![], Some(e.to_string())),
+    };
+
+    Cycle::Finished {
+        tests,
+        answers,
+        run_error,
+    }
+}
+
+fn cargo(subcommand: &str, day: Day, is_release: bool) -> std::io::Result<Output> {
+    let day_padded = day.to_string();
+    let mut args = vec![subcommand, "--bin", &day_padded];
+
+    // libtest only lists individual test results when not running quietly.
+    if subcommand != "test" {
+        args.push("--quiet");
+    }
+
+    if is_release {
+        args.push("--release");
+    }
+
+    Command::new("cargo")
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+}
+
+fn print_header(day: Day, iteration: usize, changed: &[PathBuf]) {
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} · run #{iteration}");
+    if !changed.is_empty() {
+        let names: Vec<_> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!("{ANSI_ITALIC}changed: {}{ANSI_RESET}", names.join(", "));
+    }
+    println!("------");
+}
+
+fn print_cycle(cycle: &Cycle) {
+    match cycle {
+        Cycle::BuildFailed(errors) => {
+            println!("Build: ✖");
+            println!("{}", errors.trim_end());
+        }
+        Cycle::Finished {
+            tests,
+            answers,
+            run_error,
+        } => {
+            if tests.failed.is_empty() {
+                println!("Examples: ✔ {} passed", tests.passed);
+            } else {
+                println!(
+                    "Examples: ✖ {} failed, {} passed",
+                    tests.failed.len(),
+                    tests.passed
+                );
+                for name in &tests.failed {
+                    println!("  - {name}");
+                }
+            }
+
+            for answer in answers {
+                println!("{answer}");
+            }
+
+            if let Some(error) = run_error {
+                println!("Input: ✖ {error}");
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn strip_ansi(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the escape sequence up to and including its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Parses the libtest output of `cargo test`.
+fn parse_test_output(output: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+
+    for line in output.lines() {
+        let Some(test) = line.strip_prefix("test ") else {
+            continue;
+        };
+
+        if test.ends_with(" ... ok") {
+            summary.passed += 1;
+        } else if let Some(name) = test.strip_suffix(" ... FAILED") {
+            summary.failed.push(name.to_string());
+        }
+    }
+
+    summary
+}
+
+/// Extracts the answer lines printed by the runner, e.g. `Part 1: 42 (1.2ms)`.
+fn parse_answers(output: &str) -> Vec<String> {
+    output
+        .lines()
+        // the runner overwrites intermediate results with a carriage return.
+        .filter_map(|line| line.rsplit('\r').next())
+        .map(strip_ansi)
+        .filter(|line| line.starts_with("Part "))
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, parse_test_output, Snapshot, TestSummary};
+    use std::{path::PathBuf, time::SystemTime};
+
+    #[test]
+    fn parses_test_output() {
+        let output = [
+            "running 3 tests",
+            "test tests::test_parse ... ok",
+            "test tests::test_part_one ... ok",
+            "test tests::test_part_two ... FAILED",
+            "",
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_test_output(&output),
+            TestSummary {
+                passed: 2,
+                failed: vec!["tests::test_part_two".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_answers() {
+        let output = "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)\nPart 2: ✖\rPart 2: ✖             \n";
+        assert_eq!(
+            parse_answers(output),
+            vec!["Part 1: 42 (1.2ms)", "Part 2: ✖"]
+        );
+    }
+
+    #[test]
+    fn detects_changes() {
+        let now = SystemTime::now();
+        let later = now + std::time::Duration::from_secs(1);
+
+        let old = Snapshot([(PathBuf::from("a"), now), (PathBuf::from("b"), now)].into());
+        let new = Snapshot(
+            [
+                (PathBuf::from("a"), now),
+                (PathBuf::from("b"), later),
+                (PathBuf::from("c"), now),
+            ]
+            .into(),
+        );
+
+        assert_eq!(
+            old.changes(&new),
+            vec![PathBuf::from("b"), PathBuf::from("c")]
+        );
+        assert_eq!(
+            new.changes(&old),
+            vec![PathBuf::from("b"), PathBuf::from("c")]
+        );
+        assert_eq!(old.changes(&old), Vec::<PathBuf>::new());
+    }
+}
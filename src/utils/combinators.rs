@@ -0,0 +1,152 @@
+/// Module with combinators for the common layouts of puzzle inputs: lists, lines, blank-line separated blocks and sections.
+/// Every combinator has a wrapper type that implements [`Parsable`] with it, so that nested layouts can be spelled out as a type,
+/// e.g. `Sections2<Lines<Rule>, Lines<CommaList<u32>>>`.
+use std::ops::Deref;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, line_ending, space0};
+use nom::combinator::{map, value};
+use nom::error::ParseError;
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, preceded, separated_pair, tuple};
+use nom::{IResult, Parser};
+
+use crate::utils::Parsable;
+
+/// Parses a list separated by commas, optionally followed by spaces, e.g. `1,2,3` or `r, wr, b`.
+pub fn comma_list<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_list1(pair(char(','), space0), f)
+}
+
+/// Parses one item per line. Stops before a blank line, so that it can be followed by another section.
+pub fn lines_of<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, f)
+}
+
+/// Parses the end of a line followed by one or more empty lines.
+pub fn blank_line<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
+    value((), pair(line_ending, many1(line_ending)))(input)
+}
+
+/// Parses blocks of lines that are separated by blank lines.
+pub fn blocks<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_list1(blank_line, f)
+}
+
+/// Parses two sections of different shape that are separated by blank lines.
+pub fn sections2<'a, O1, O2, E, F1, F2>(first: F1, second: F2) -> impl FnMut(&'a str) -> IResult<&'a str, (O1, O2), E>
+where
+    F1: Parser<&'a str, O1, E>,
+    F2: Parser<&'a str, O2, E>,
+    E: ParseError<&'a str>,
+{
+    separated_pair(first, blank_line, second)
+}
+
+/// Parses three sections of different shape that are separated by blank lines.
+pub fn sections3<'a, O1, O2, O3, E, F1, F2, F3>(first: F1, second: F2, third: F3) -> impl FnMut(&'a str) -> IResult<&'a str, (O1, O2, O3), E>
+where
+    F1: Parser<&'a str, O1, E>,
+    F2: Parser<&'a str, O2, E>,
+    F3: Parser<&'a str, O3, E>,
+    E: ParseError<&'a str>,
+{
+    tuple((first, preceded(blank_line, second), preceded(blank_line, third)))
+}
+
+/// Parses a key and a value with a separator in between, which may be surrounded by spaces, e.g. `Register A: 729`.
+pub fn key_value<'a, K, V, E, FK, FV>(key: FK, separator: &'a str, value: FV) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V), E>
+where
+    FK: Parser<&'a str, K, E>,
+    FV: Parser<&'a str, V, E>,
+    E: ParseError<&'a str>,
+{
+    separated_pair(key, tuple((space0, tag(separator), space0)), value)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A list parsed with [`comma_list`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommaList<T>(pub Vec<T>);
+
+/// Items parsed with [`lines_of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines<T>(pub Vec<T>);
+
+/// Blocks parsed with [`blocks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocks<T>(pub Vec<T>);
+
+/// Sections parsed with [`sections2`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections2<A, B>(pub A, pub B);
+
+/// Sections parsed with [`sections3`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections3<A, B, C>(pub A, pub B, pub C);
+
+/// A line like `key: value`, parsed with [`key_value`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValue<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+macro_rules! impl_list_wrapper {
+    ($($wrapper:ident => $combinator:ident),+) => {
+        $(
+            impl<'a, T: Parsable<'a>> Parsable<'a> for $wrapper<T> {
+                fn parse(input: &'a str) -> IResult<&'a str, Self> {
+                    map($combinator(T::parse), $wrapper)(input)
+                }
+            }
+
+            impl<T> Deref for $wrapper<T> {
+                type Target = Vec<T>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl<T> From<$wrapper<T>> for Vec<T> {
+                fn from(value: $wrapper<T>) -> Self {
+                    value.0
+                }
+            }
+        )+
+    };
+}
+
+impl_list_wrapper!(CommaList => comma_list, Lines => lines_of, Blocks => blocks);
+
+impl<'a, A: Parsable<'a>, B: Parsable<'a>> Parsable<'a> for Sections2<A, B> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        map(sections2(A::parse, B::parse), |(a, b)| Sections2(a, b))(input)
+    }
+}
+
+impl<'a, A: Parsable<'a>, B: Parsable<'a>, C: Parsable<'a>> Parsable<'a> for Sections3<A, B, C> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        map(sections3(A::parse, B::parse, C::parse), |(a, b, c)| Sections3(a, b, c))(input)
+    }
+}
+
+impl<'a, K: Parsable<'a>, V: Parsable<'a>> Parsable<'a> for KeyValue<K, V> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        map(key_value(K::parse, ":", V::parse), |(key, value)| KeyValue { key, value })(input)
+    }
+}
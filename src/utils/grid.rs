@@ -0,0 +1,169 @@
+/// Module with a read-only view of a grid shaped input that borrows the input instead of parsing it into rows.
+use std::fmt::{Debug, Formatter};
+
+use crate::utils::location::{Access2d, Location, SquareIterator};
+
+/// A grid of bytes that borrows the puzzle input, e.g. for maps of `.` and `#`.
+///
+/// The width is taken from the first line, every row is expected to have the same width. Lines may end with `\n` or `\r\n`, and a
+/// trailing line ending is optional.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    stride: usize,
+    height: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let bytes = input.trim_end_matches(['\r', '\n']).as_bytes();
+
+        let (width, stride) = match bytes.iter().position(|&b| b == b'\n') {
+            Some(newline) if newline > 0 && bytes[newline - 1] == b'\r' => (newline - 1, newline + 1),
+            Some(newline) => (newline, newline + 1),
+            None => (bytes.len(), bytes.len() + 1),
+        };
+
+        let height = if bytes.is_empty() { 0 } else { (bytes.len() + stride - width) / stride };
+
+        Self {
+            bytes,
+            width,
+            stride,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Location<i32> {
+        Location::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, loc: Location<i32>) -> bool {
+        self.index(loc).is_some()
+    }
+
+    pub fn get(&self, loc: Location<i32>) -> Option<u8> {
+        self.index(loc).map(|index| self.bytes[index])
+    }
+
+    /// Returns the row `y` without its line ending.
+    pub fn row(&self, y: usize) -> Option<&'a [u8]> {
+        (y < self.height).then(|| &self.bytes[y * self.stride..y * self.stride + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// Returns the location of the first occurrence of `byte`, row by row.
+    pub fn find(&self, byte: u8) -> Option<Location<i32>> {
+        self.iter().find(|&(_, b)| b == byte).map(|(loc, _)| loc)
+    }
+
+    /// Iterates over all locations and their bytes, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Location<i32>, u8)> + '_ {
+        self.iter_2d_keys().map(|loc| (loc, self.bytes[self.offset(loc)]))
+    }
+
+    /// Iterates over the up to four orthogonal neighbours of `loc` that are inside the grid.
+    pub fn neighbours(&self, loc: Location<i32>) -> impl Iterator<Item = (Location<i32>, u8)> + '_ {
+        loc.iter_adjacent().into_iter().filter_map(|loc| Some((loc, self.get(loc)?)))
+    }
+
+    /// Iterates over the bytes from `start` in `direction` until the ray leaves the grid. Includes the byte at `start`.
+    pub fn ray(&self, start: Location<i32>, direction: Location<i32>) -> impl Iterator<Item = u8> + '_ {
+        start.iter_ray(direction).map_while(|loc| self.get(loc))
+    }
+
+    fn index(&self, loc: Location<i32>) -> Option<usize> {
+        let x = usize::try_from(loc.x).ok()?;
+        let y = usize::try_from(loc.y).ok()?;
+
+        (x < self.width && y < self.height).then(|| y * self.stride + x)
+    }
+
+    fn offset(&self, loc: Location<i32>) -> usize {
+        loc.y as usize * self.stride + loc.x as usize
+    }
+}
+
+impl Access2d<u8> for ByteGrid<'_> {
+    fn get_2d(&self, loc: Location<i32>) -> Option<&u8> {
+        self.index(loc).map(|index| &self.bytes[index])
+    }
+
+    /// The grid borrows the input, so it cannot be changed. Always returns `None`.
+    fn set_2d(&mut self, _loc: Location<i32>, _element: u8) -> Option<()> {
+        None
+    }
+
+    fn iter_2d_keys(&self) -> SquareIterator<i32> {
+        Location::new(0, 0).iter_range(self.size())
+    }
+}
+
+impl Debug for ByteGrid<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::utils::location::direction::{DOWN, RIGHT};
+
+    #[test]
+    fn measures_grid() {
+        let grid = ByteGrid::new("abc\ndef\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Location::new(2, 1)), Some(b'f'));
+        assert_eq!(grid.get(Location::new(3, 0)), None);
+        assert_eq!(grid.get(Location::new(0, 2)), None);
+        assert_eq!(grid.get(Location::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn handles_crlf_and_missing_trailing_newline() {
+        for input in ["abc\r\ndef\r\n", "abc\r\ndef", "abc\ndef"] {
+            let grid = ByteGrid::new(input);
+
+            assert_eq!(grid.size(), Location::new(3, 2), "{input:?}");
+            assert_eq!(grid.rows().collect::<Vec<_>>(), vec![b"abc", b"def"], "{input:?}");
+        }
+    }
+
+    #[test]
+    fn handles_single_line_and_empty_input() {
+        assert_eq!(ByteGrid::new("abc").size(), Location::new(3, 1));
+        assert_eq!(ByteGrid::new("").size(), Location::new(0, 0));
+        assert_eq!(ByteGrid::new("").iter().count(), 0);
+    }
+
+    #[test]
+    fn looks_up_neighbours_and_rays() {
+        let grid = ByteGrid::new("abc\ndef\nghi\n");
+
+        assert_eq!(grid.find(b'e'), Some(Location::new(1, 1)));
+        assert_eq!(grid.neighbours(Location::new(0, 0)).map(|(_, b)| b).collect::<Vec<_>>(), vec![b'd', b'b']);
+        assert_eq!(grid.neighbours(Location::new(1, 1)).count(), 4);
+        assert_eq!(grid.ray(Location::new(0, 0), RIGHT + DOWN).collect::<Vec<_>>(), b"aei");
+        assert_eq!(grid.get_2d(Location::new(2, 2)), Some(&b'i'));
+    }
+}
@@ -6,6 +6,7 @@ use std::ops::{Add, Div, Mul, Neg, RangeInclusive, Rem, Sub};
 use num::traits::Euclid;
 use num::{one, zero, Bounded, Num, Signed, Zero};
 
+#[allow(clippy::derive_ord_xor_partial_ord)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord)]
 pub struct Location<T: Num> {
     pub x: T,
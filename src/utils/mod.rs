@@ -1,21 +1,36 @@
+pub mod combinators;
+pub mod grid;
 pub mod location;
+pub mod numbers;
+pub mod ocr;
+pub mod property;
+pub mod random;
+pub mod simulation;
+pub mod terminal;
+pub mod viz;
 
 use std::str::FromStr;
 
-use nom::bytes::complete::is_a;
-use nom::character::complete::{digit1, line_ending, space1};
-use nom::combinator::{all_consuming, map_res, opt, recognize};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alphanumeric1, char, digit1, line_ending, none_of, one_of, space1};
+use nom::combinator::{all_consuming, map, map_res, opt, recognize, value};
 use nom::error::ParseError;
 use nom::multi::separated_list1;
-use nom::sequence::{pair, terminated};
+use nom::sequence::{pair, preceded, separated_pair, terminated};
 use nom::{IResult, Parser};
+use num::Num;
+
+use crate::utils::location::Location;
+
+pub use combinators::*;
 
 pub fn parse_input_by_lines<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
 where
     F: Parser<&'a str, O, E>,
     E: ParseError<&'a str>,
 {
-    parse_input(separated_list1(line_ending, f))
+    parse_input(lines_of(f))
 }
 
 pub fn parse_input<'a, O, E, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
@@ -31,6 +46,8 @@ pub fn end_of_file(input: &str) -> IResult<&str, ()> {
     Ok((input, ()))
 }
 
+pub use advent_of_code_derive::Parsable;
+
 pub trait Parsable<'a> {
     fn parse(input: &'a str) -> IResult<&'a str, Self>
     where
@@ -56,7 +73,7 @@ macro_rules! impl_parsable_int {
         $(
             impl<'a> Parsable<'a> for $t {
                 fn parse(input: &str) -> IResult<&str, Self> {
-                    map_res(recognize(pair(opt(is_a("-")), digit1)), Self::from_str)(input)
+                    map_res(recognize(pair(opt(one_of("+-")), digit1)), Self::from_str)(input)
                 }
             }
         )+
@@ -69,4 +86,104 @@ impl<'a, T: Parsable<'a>> Parsable<'a> for Vec<T> {
     fn parse(input: &'a str) -> IResult<&'a str, Self> {
         separated_list1(space1, T::parse)(input)
     }
-}
\ No newline at end of file
+}
+impl<'a, T: Parsable<'a>> Parsable<'a> for Option<T> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        opt(T::parse)(input)
+    }
+}
+
+/// Parses any character but a line ending.
+impl<'a> Parsable<'a> for char {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        none_of("\r\n")(input)
+    }
+}
+
+impl<'a> Parsable<'a> for bool {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        alt((value(true, tag("true")), value(false, tag("false"))))(input)
+    }
+}
+
+/// Parses a word of letters and digits.
+impl<'a> Parsable<'a> for &'a str {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        alphanumeric1(input)
+    }
+}
+
+/// Parses a word of letters and digits.
+impl<'a> Parsable<'a> for String {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        map(alphanumeric1, String::from)(input)
+    }
+}
+
+/// Parses a location in the common `x,y` form.
+impl<'a, T: Parsable<'a> + Num> Parsable<'a> for Location<T> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        map(separated_pair(T::parse, char(','), T::parse), |(x, y)| Location::new(x, y))(input)
+    }
+}
+
+/// Types made of a fixed number of values with a separator in between, like tuples and arrays.
+/// Their [`Parsable`] implementation separates the values with a comma, [`separated`] allows any other separator.
+pub trait SeparatedParsable<'a>: Sized {
+    fn parse_separated(input: &'a str, separator: &str) -> IResult<&'a str, Self>;
+}
+
+/// Parses a tuple or array whose values are separated by `separator`, e.g. `separated::<(u32, u32)>("|")` for `47|53`.
+pub fn separated<'a, T: SeparatedParsable<'a>>(separator: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    move |input| T::parse_separated(input, separator)
+}
+
+macro_rules! impl_parsable_tuple {
+    ($first:ident $first_value:ident $(, $t:ident $value:ident)+) => {
+        impl<'a, $first: Parsable<'a>, $( $t: Parsable<'a> ),+> SeparatedParsable<'a> for ($first, $( $t ),+) {
+            fn parse_separated(input: &'a str, separator: &str) -> IResult<&'a str, Self> {
+                let (input, $first_value) = $first::parse(input)?;
+                $( let (input, $value) = preceded(tag(separator), $t::parse)(input)?; )+
+
+                Ok((input, ($first_value, $( $value ),+)))
+            }
+        }
+
+        impl<'a, $first: Parsable<'a>, $( $t: Parsable<'a> ),+> Parsable<'a> for ($first, $( $t ),+) {
+            fn parse(input: &'a str) -> IResult<&'a str, Self> {
+                Self::parse_separated(input, ",")
+            }
+        }
+    };
+}
+
+impl_parsable_tuple!(A a, B b);
+impl_parsable_tuple!(A a, B b, C c);
+impl_parsable_tuple!(A a, B b, C c, D d);
+
+impl<'a, T: Parsable<'a>, const N: usize> SeparatedParsable<'a> for [T; N] {
+    fn parse_separated(mut input: &'a str, separator: &str) -> IResult<&'a str, Self> {
+        let mut values = Vec::with_capacity(N);
+
+        for i in 0..N {
+            if i > 0 {
+                (input, _) = tag(separator)(input)?;
+            }
+
+            let (rest, value) = T::parse(input)?;
+            values.push(value);
+            input = rest;
+        }
+
+        let Ok(values) = values.try_into() else {
+            unreachable!("exactly N values were parsed")
+        };
+        Ok((input, values))
+    }
+}
+
+impl<'a, T: Parsable<'a>, const N: usize> Parsable<'a> for [T; N] {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        Self::parse_separated(input, ",")
+    }
+}
@@ -0,0 +1,158 @@
+/// Module that extracts integers from text without going through nom, for inputs that are just "some numbers with noise in between".
+use std::marker::PhantomData;
+
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+/// An integer type that [`numbers`] can produce. Signed types take a `-` directly in front of the digits into account.
+pub trait Integer: Copy + Default {
+    const SIGNED: bool;
+
+    /// Converts ASCII digits into a number. Overflows wrap around.
+    fn from_digits(digits: &[u8], negative: bool) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+
+                #[inline]
+                fn from_digits(digits: &[u8], negative: bool) -> Self {
+                    let value = digits.iter().fold(0 as $t, |acc, &digit| acc.wrapping_mul(10).wrapping_add((digit - b'0') as $t));
+
+                    if negative {
+                        value.wrapping_neg()
+                    } else {
+                        value
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+impl_integer!(true: i8, i16, i32, i64, i128, isize);
+
+/// Iterator over the integers in some text, created by [`numbers`].
+#[derive(Debug, Clone)]
+pub struct Numbers<'a, T> {
+    bytes: &'a [u8],
+    position: usize,
+    integer: PhantomData<T>,
+}
+
+impl<T: Integer> Numbers<'_, T> {
+    /// Returns the next `N` integers, or `None` if there are fewer left.
+    pub fn next_array<const N: usize>(&mut self) -> Option<[T; N]> {
+        let mut array = [T::default(); N];
+
+        for element in array.iter_mut() {
+            *element = self.next()?;
+        }
+
+        Some(array)
+    }
+
+    /// Number of bytes consumed so far, i.e. the position right after the last returned integer, or the end of the input once the
+    /// iterator is exhausted. Always a char boundary.
+    pub fn offset(&self) -> usize {
+        self.position
+    }
+}
+
+impl<T: Integer> Iterator for Numbers<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+
+        let start = match bytes[self.position..].iter().position(u8::is_ascii_digit) {
+            Some(skipped) => self.position + skipped,
+            None => {
+                self.position = bytes.len();
+                return None;
+            }
+        };
+
+        let end = bytes[start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |length| start + length);
+        let negative = T::SIGNED && start > 0 && bytes[start - 1] == b'-';
+
+        self.position = end;
+
+        Some(T::from_digits(&bytes[start..end], negative))
+    }
+}
+
+/// Iterates over all integers in `input`, skipping everything that is not a digit, e.g. `numbers::<i32>("p=0,4 v=-3,3")` yields
+/// `0, 4, -3, 3`. Does not allocate.
+pub fn numbers<T: Integer>(input: &(impl AsRef<[u8]> + ?Sized)) -> Numbers<'_, T> {
+    Numbers {
+        bytes: input.as_ref(),
+        position: 0,
+        integer: PhantomData,
+    }
+}
+
+/// Returns the first `N` signed integers of `input`, e.g. `let [x, y, dx, dy] = ints::<4>(line)?;`.
+pub fn ints<const N: usize>(input: &(impl AsRef<[u8]> + ?Sized)) -> Option<[i64; N]> {
+    numbers(input).next_array()
+}
+
+/// Returns the first `N` unsigned integers of `input`.
+pub fn uints<const N: usize>(input: &(impl AsRef<[u8]> + ?Sized)) -> Option<[u64; N]> {
+    numbers(input).next_array()
+}
+
+/// A nom parser that takes the next `N` integers, skipping everything that is not a digit. A fast path for the parts of an input that
+/// only need their numbers, e.g. `map(parse_ints, |[x, y]| Location::new(x, y))`.
+pub fn parse_ints<T: Integer, const N: usize>(input: &str) -> IResult<&str, [T; N]> {
+    let mut numbers = numbers(input);
+
+    match numbers.next_array() {
+        Some(array) => Ok((&input[numbers.offset()..], array)),
+        None => Err(nom::Err::Error(Error::new(input, ErrorKind::Digit))),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_numbers() {
+        assert_eq!(numbers::<i32>("p=0,4 v=-3,3").collect::<Vec<_>>(), vec![0, 4, -3, 3]);
+        assert_eq!(numbers::<u32>("p=0,4 v=-3,3").collect::<Vec<_>>(), vec![0, 4, 3, 3]);
+        assert_eq!(numbers::<u8>(b"12 a34b\r\n5".as_slice()).collect::<Vec<_>>(), vec![12, 34, 5]);
+        assert_eq!(numbers::<i64>("no digits - here").next(), None);
+        assert_eq!(numbers::<i64>("").next(), None);
+    }
+
+    #[test]
+    fn extracts_extreme_values() {
+        let input = format!("{} {} {}", i64::MIN, i64::MAX, u64::MAX);
+
+        assert_eq!(numbers::<i64>(&input).take(2).collect::<Vec<_>>(), vec![i64::MIN, i64::MAX]);
+        assert_eq!(numbers::<u64>(&input).last(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn extracts_arrays() {
+        assert_eq!(ints::<4>("p=0,4 v=-3,3"), Some([0, 4, -3, 3]));
+        assert_eq!(uints::<2>("1,2,3"), Some([1, 2]));
+        assert_eq!(ints::<3>("1,2"), None);
+    }
+
+    #[test]
+    fn parses_with_nom() {
+        assert_eq!(parse_ints::<u32, 2>("X+1, Y+2\nrest"), Ok(("\nrest", [1, 2])));
+        assert_eq!(parse_ints::<u32, 2>("X+1"), Err(nom::Err::Error(Error::new("X+1", ErrorKind::Digit))));
+    }
+}
@@ -0,0 +1,199 @@
+/// Module that reads the block letters some puzzles draw as their answer, so that those days can return a `String`.
+/// Knows the letters of the 4x6 and the 6x10 font used by Advent of Code.
+use std::ops::Range;
+
+use crate::utils::grid::ByteGrid;
+use crate::utils::location::Location;
+
+/// A letter and its rows, `#` marks lit pixels.
+type Glyph = (char, &'static [&'static str]);
+
+#[rustfmt::skip]
+const FONT_6: &[Glyph] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const FONT_10: &[Glyph] = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Reads the letters drawn by the lit pixels of `pixels`, indexed as `pixels[y][x]`.
+///
+/// The font is chosen by the height of the lit area, letters are separated by columns without lit pixels. Returns `None` if the
+/// height does not match a font or a letter is unknown.
+pub fn recognize(pixels: &[Vec<bool>]) -> Option<String> {
+    let is_lit = |x: usize, y: usize| pixels[y].get(x).copied().unwrap_or(false);
+
+    let lit_rows: Vec<usize> = (0..pixels.len()).filter(|&y| pixels[y].contains(&true)).collect();
+    let rows = *lit_rows.first()?..*lit_rows.last()? + 1;
+
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    let width = pixels.iter().map(Vec::len).max()?;
+    let lit_columns: Vec<bool> = (0..width).map(|x| rows.clone().any(|y| is_lit(x, y))).collect();
+
+    letter_columns(&lit_columns)
+        .map(|columns| {
+            font.iter()
+                .find(|(_, glyph)| matches_glyph(glyph, &columns, |x, y| is_lit(x, rows.start + y)))
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+/// Reads the letters drawn by a set of lit locations, e.g. the points of a message in the sky.
+pub fn recognize_locations<'a>(locations: impl IntoIterator<Item = &'a Location<i32>>) -> Option<String> {
+    let locations: Vec<_> = locations.into_iter().copied().collect();
+
+    let min = Location::new(locations.iter().map(|loc| loc.x).min()?, locations.iter().map(|loc| loc.y).min()?);
+    let max = Location::new(locations.iter().map(|loc| loc.x).max()?, locations.iter().map(|loc| loc.y).max()?);
+
+    let mut pixels = vec![vec![false; (max.x - min.x + 1) as usize]; (max.y - min.y + 1) as usize];
+    for loc in locations {
+        pixels[(loc.y - min.y) as usize][(loc.x - min.x) as usize] = true;
+    }
+
+    recognize(&pixels)
+}
+
+/// Reads the letters drawn by the bytes equal to `lit` in a grid, e.g. `recognize_grid(&grid, b'#')`.
+pub fn recognize_grid(grid: &ByteGrid, lit: u8) -> Option<String> {
+    let pixels: Vec<Vec<bool>> = grid.rows().map(|row| row.iter().map(|&b| b == lit).collect()).collect();
+
+    recognize(&pixels)
+}
+
+/// Splits the columns into runs of lit columns, one per letter.
+fn letter_columns(lit_columns: &[bool]) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut x = 0;
+
+    std::iter::from_fn(move || {
+        let start = x + lit_columns[x..].iter().position(|&lit| lit)?;
+        let end = lit_columns[start..]
+            .iter()
+            .position(|&lit| !lit)
+            .map_or(lit_columns.len(), |length| start + length);
+        x = end;
+
+        Some(start..end)
+    })
+}
+
+/// Checks whether the pixels in `columns` show `glyph`. Empty columns around the glyph are ignored, like they are for the input.
+fn matches_glyph(glyph: &[&str], columns: &Range<usize>, is_lit: impl Fn(usize, usize) -> bool) -> bool {
+    let glyph_lit = |x: usize, y: usize| glyph[y].as_bytes()[x] == b'#';
+    let glyph_width = glyph[0].len();
+
+    let Some(first) = (0..glyph_width).find(|&x| (0..glyph.len()).any(|y| glyph_lit(x, y))) else {
+        return false;
+    };
+    let last = (0..glyph_width)
+        .rev()
+        .find(|&x| (0..glyph.len()).any(|y| glyph_lit(x, y)))
+        .unwrap_or(first);
+
+    last - first + 1 == columns.len() && (0..glyph.len()).all(|y| columns.clone().enumerate().all(|(i, x)| glyph_lit(first + i, y) == is_lit(x, y)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    /// Draws `text` with `font`, one empty column between letters.
+    fn draw(text: &str, font: &[Glyph]) -> Vec<Vec<bool>> {
+        let height = font[0].1.len();
+        let mut pixels = vec![vec![]; height];
+
+        for letter in text.chars() {
+            let (_, glyph) = font.iter().find(|(c, _)| *c == letter).unwrap();
+
+            for (row, glyph_row) in pixels.iter_mut().zip(glyph.iter()) {
+                row.extend(glyph_row.bytes().map(|b| b == b'#'));
+                row.push(false);
+            }
+        }
+
+        pixels
+    }
+
+    #[test]
+    fn recognizes_every_letter() {
+        for font in [FONT_6, FONT_10] {
+            let text: String = font.iter().map(|(letter, _)| letter).collect();
+            assert_eq!(recognize(&draw(&text, font)), Some(text));
+        }
+    }
+
+    #[test]
+    fn ignores_empty_border() {
+        let mut pixels = draw("HI", FONT_6);
+        pixels.insert(0, vec![false; 3]);
+        pixels.iter_mut().for_each(|row| row.insert(0, false));
+
+        assert_eq!(recognize(&pixels), Some("HI".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        assert_eq!(recognize(&[]), None);
+        assert_eq!(recognize(&vec![vec![true; 4]; 5]), None);
+        assert_eq!(recognize(&vec![vec![true; 4]; 6]), None);
+    }
+
+    #[test]
+    fn recognizes_locations_and_grids() {
+        let locations: Vec<_> = draw("ZJ", FONT_10)
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &lit)| lit)
+                    .map(move |(x, _)| Location::new(x as i32 + 7, y as i32 - 3))
+            })
+            .collect();
+
+        assert_eq!(recognize_locations(&locations), Some("ZJ".to_string()));
+
+        let grid = "#..#.####.\n#..#.#....\n####.###..\n#..#.#....\n#..#.#....\n#..#.####.\n";
+        assert_eq!(recognize_grid(&ByteGrid::new(grid), b'#'), Some("HE".to_string()));
+    }
+}
@@ -0,0 +1,609 @@
+/// Module for property-based tests: values are generated from a seeded [`Rng`],
+/// and a failing value is shrunk to a minimal counterexample before it is reported.
+use std::env;
+use std::fmt::Debug;
+
+use num::Num;
+
+use crate::utils::combinators::{Blocks, CommaList, KeyValue, Lines, Sections2, Sections3};
+use crate::utils::location::Location;
+use crate::utils::random::Rng;
+use crate::utils::Parsable;
+
+/// Seed used when `AOC_PROPERTY_SEED` is not set, so that failures are reproducible.
+const DEFAULT_SEED: u64 = 2024;
+const CASES: usize = 256;
+const MAX_SHRINK_STEPS: usize = 1000;
+
+/// Types that can be randomly generated for property tests.
+pub trait Arbitrary: Sized {
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// Returns smaller variants of this value, most aggressive first. Shrinking stops at values without smaller variants.
+    fn shrink(&self) -> Vec<Self> {
+        vec![]
+    }
+}
+
+/// The counterpart of [`Parsable`]: renders a value in the format its parser expects.
+pub trait Printable {
+    fn print(&self) -> String;
+}
+
+/// Checks `property` for random values, seeded with `AOC_PROPERTY_SEED` or a fixed default.
+/// Panics with the shrunk counterexample if the property does not hold.
+pub fn check<T: Arbitrary + Debug>(property: impl Fn(&T) -> bool) {
+    let seed = env::var("AOC_PROPERTY_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+
+    check_with(seed, CASES, property);
+}
+
+pub fn check_with<T: Arbitrary + Debug>(seed: u64, cases: usize, property: impl Fn(&T) -> bool) {
+    let mut rng = Rng::new(seed);
+
+    for case in 1..=cases {
+        let value = T::arbitrary(&mut rng);
+
+        if !property(&value) {
+            let original = format!("{value:?}");
+            let (shrunk, steps) = shrink_failure(value, &property);
+
+            panic!("property failed on case {case} with seed {seed}\n  counterexample: {shrunk:?}\n  shrunk from ({steps} steps): {original}");
+        }
+    }
+}
+
+fn shrink_failure<T: Arbitrary>(mut value: T, property: &impl Fn(&T) -> bool) -> (T, usize) {
+    let mut steps = 0;
+
+    while steps < MAX_SHRINK_STEPS {
+        match value
+            .shrink()
+            .into_iter()
+            .find(|candidate| !property(candidate))
+        {
+            Some(smaller) => {
+                value = smaller;
+                steps += 1;
+            }
+            None => break,
+        }
+    }
+
+    (value, steps)
+}
+
+/// Asserts that parsing a printed value yields the value again and consumes the whole output.
+pub fn assert_round_trip<T>()
+where
+    T: for<'a> Parsable<'a> + Printable + Arbitrary + PartialEq + Debug,
+{
+    check(|value: &T| {
+        let printed = value.print();
+        matches!(T::parse(&printed), Ok(("", parsed)) if parsed == *value)
+    });
+}
+
+/// Joins lines with randomly chosen `\n` or `\r\n` endings and an optional trailing line ending, like inputs saved on different platforms.
+pub fn vary_line_endings(lines: &[String], rng: &mut Rng) -> String {
+    let mut output = String::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        output.push_str(line);
+
+        if i + 1 < lines.len() || rng.chance(0.5) {
+            output.push_str(if rng.chance(0.5) { "\r\n" } else { "\n" });
+        }
+    }
+
+    output
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.chance(0.5)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Integers are small most of the time, as edge cases tend to hide there, but may span the whole range.
+macro_rules! impl_arbitrary_int {
+    (for $($t:ty),+) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    match rng.index(3) {
+                        0 => rng.range(0..10) as $t,
+                        1 => rng.range(0..1000) as $t,
+                        _ => rng.next_u64() as $t,
+                    }
+                }
+
+                #[allow(unused_comparisons)]
+                fn shrink(&self) -> Vec<Self> {
+                    let value = *self;
+                    let mut candidates = vec![];
+
+                    if value == 0 {
+                        return candidates;
+                    }
+
+                    if value < 0 {
+                        candidates.push(value.wrapping_neg());
+                    }
+
+                    candidates.extend([0, value / 2, if value > 0 { value - 1 } else { value + 1 }]);
+                    candidates.dedup();
+                    candidates.retain(|candidate| *candidate != value);
+                    candidates
+                }
+            }
+        )+
+    };
+}
+
+impl_arbitrary_int!(for u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Vectors are never empty, like the lists in puzzle inputs. [`Parsable`] for `Vec<T>` relies on that.
+impl<T: Arbitrary + Clone> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let len = rng.range(1..9) as usize;
+        (0..len).map(|_| T::arbitrary(rng)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        if self.len() > 1 {
+            let (left, right) = self.split_at(self.len() / 2);
+            candidates.push(left.to_vec());
+            candidates.push(right.to_vec());
+
+            for i in 0..self.len() {
+                let mut smaller = self.clone();
+                smaller.remove(i);
+                candidates.push(smaller);
+            }
+        }
+
+        for (i, item) in self.iter().enumerate() {
+            for smaller_item in item.shrink() {
+                let mut smaller = self.clone();
+                smaller[i] = smaller_item;
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<A: Arbitrary + Clone, B: Arbitrary + Clone> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        (A::arbitrary(rng), B::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+
+        a.shrink()
+            .into_iter()
+            .map(|a| (a, b.clone()))
+            .chain(b.shrink().into_iter().map(|b| (a.clone(), b)))
+            .collect()
+    }
+}
+
+impl<A: Arbitrary + Clone, B: Arbitrary + Clone, C: Arbitrary + Clone> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        (A::arbitrary(rng), B::arbitrary(rng), C::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+
+        ((a.clone(), b.clone()), c.clone())
+            .shrink()
+            .into_iter()
+            .map(|((a, b), c)| (a, b, c))
+            .collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.chance(0.75).then(|| T::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            Some(value) => [None].into_iter().chain(value.shrink().into_iter().map(Some)).collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// Characters are printable ASCII, which covers the tiles and markers of puzzle inputs.
+impl Arbitrary for char {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.range(b'!' as u64..b'~' as u64 + 1) as u8 as char
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' {
+            vec![]
+        } else {
+            vec!['a']
+        }
+    }
+}
+
+/// Strings are words of letters and digits, like names in puzzle inputs.
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+        let len = rng.range(1..9) as usize;
+        (0..len).map(|_| ALPHABET[rng.index(ALPHABET.len())] as char).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+
+        if chars.len() > 1 {
+            vec![chars[..chars.len() / 2].iter().collect(), chars[1..].iter().collect()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl<T: Arbitrary + Clone, const N: usize> Arbitrary for [T; N] {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        std::array::from_fn(|_| T::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        for (i, item) in self.iter().enumerate() {
+            for smaller_item in item.shrink() {
+                let mut smaller = self.clone();
+                smaller[i] = smaller_item;
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
+}
+
+macro_rules! impl_arbitrary_list_wrapper {
+    ($($wrapper:ident),+) => {
+        $(
+            impl<T: Arbitrary + Clone> Arbitrary for $wrapper<T> {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    $wrapper(Vec::arbitrary(rng))
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    self.0.shrink().into_iter().map($wrapper).collect()
+                }
+            }
+        )+
+    };
+}
+
+impl_arbitrary_list_wrapper!(CommaList, Lines, Blocks);
+
+impl<A: Arbitrary + Clone, B: Arbitrary + Clone> Arbitrary for Sections2<A, B> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let (a, b) = Arbitrary::arbitrary(rng);
+        Sections2(a, b)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        (self.0.clone(), self.1.clone())
+            .shrink()
+            .into_iter()
+            .map(|(a, b)| Sections2(a, b))
+            .collect()
+    }
+}
+
+impl<A: Arbitrary + Clone, B: Arbitrary + Clone, C: Arbitrary + Clone> Arbitrary for Sections3<A, B, C> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let (a, b, c) = Arbitrary::arbitrary(rng);
+        Sections3(a, b, c)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        (self.0.clone(), self.1.clone(), self.2.clone())
+            .shrink()
+            .into_iter()
+            .map(|(a, b, c)| Sections3(a, b, c))
+            .collect()
+    }
+}
+
+impl<K: Arbitrary + Clone, V: Arbitrary + Clone> Arbitrary for KeyValue<K, V> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let (key, value) = Arbitrary::arbitrary(rng);
+        KeyValue { key, value }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        (self.key.clone(), self.value.clone())
+            .shrink()
+            .into_iter()
+            .map(|(key, value)| KeyValue { key, value })
+            .collect()
+    }
+}
+
+impl<T: Arbitrary + Num + Copy> Arbitrary for Location<T> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Location::new(T::arbitrary(rng), T::arbitrary(rng))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        (self.x, self.y)
+            .shrink()
+            .into_iter()
+            .map(|(x, y)| Location::new(x, y))
+            .collect()
+    }
+}
+
+macro_rules! impl_printable_int {
+    (for $($t:ty),+) => {
+        $(
+            impl Printable for $t {
+                fn print(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )+
+    };
+}
+
+impl_printable_int!(for u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T: Printable> Printable for Vec<T> {
+    fn print(&self) -> String {
+        self.iter().map(T::print).collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl<T: Printable> Printable for Option<T> {
+    fn print(&self) -> String {
+        self.as_ref().map(T::print).unwrap_or_default()
+    }
+}
+
+impl Printable for char {
+    fn print(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Printable for bool {
+    fn print(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Printable for String {
+    fn print(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: Printable + Num> Printable for Location<T> {
+    fn print(&self) -> String {
+        format!("{},{}", self.x.print(), self.y.print())
+    }
+}
+
+impl<A: Printable, B: Printable> Printable for (A, B) {
+    fn print(&self) -> String {
+        format!("{},{}", self.0.print(), self.1.print())
+    }
+}
+
+impl<A: Printable, B: Printable, C: Printable> Printable for (A, B, C) {
+    fn print(&self) -> String {
+        format!("{},{},{}", self.0.print(), self.1.print(), self.2.print())
+    }
+}
+
+impl<T: Printable> Printable for CommaList<T> {
+    fn print(&self) -> String {
+        self.iter().map(T::print).collect::<Vec<_>>().join(",")
+    }
+}
+
+impl<T: Printable> Printable for Lines<T> {
+    fn print(&self) -> String {
+        self.iter().map(T::print).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<T: Printable> Printable for Blocks<T> {
+    fn print(&self) -> String {
+        self.iter().map(T::print).collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+impl<A: Printable, B: Printable> Printable for Sections2<A, B> {
+    fn print(&self) -> String {
+        format!("{}\n\n{}", self.0.print(), self.1.print())
+    }
+}
+
+impl<A: Printable, B: Printable, C: Printable> Printable for Sections3<A, B, C> {
+    fn print(&self) -> String {
+        format!("{}\n\n{}\n\n{}", self.0.print(), self.1.print(), self.2.print())
+    }
+}
+
+impl<K: Printable, V: Printable> Printable for KeyValue<K, V> {
+    fn print(&self) -> String {
+        format!("{}: {}", self.key.print(), self.value.print())
+    }
+}
+
+impl<T: Printable, const N: usize> Printable for [T; N] {
+    fn print(&self) -> String {
+        self.iter().map(T::print).collect::<Vec<_>>().join(",")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{assert_round_trip, check, check_with, vary_line_endings, Arbitrary};
+    use crate::utils::combinators::{Blocks, CommaList, KeyValue, Lines, Sections2, Sections3};
+    use crate::utils::location::Location;
+    use crate::utils::random::Rng;
+    use crate::utils::{parse_input, parse_input_by_lines, separated, Parsable};
+
+    #[test]
+    fn round_trips_numbers() {
+        assert_round_trip::<u8>();
+        assert_round_trip::<u64>();
+        assert_round_trip::<i32>();
+        assert_round_trip::<i128>();
+        assert_round_trip::<Vec<u32>>();
+        assert_round_trip::<Vec<i64>>();
+    }
+
+    #[test]
+    fn round_trips_composite_values() {
+        assert_round_trip::<(u32, i64)>();
+        assert_round_trip::<(u8, u8, u8)>();
+        assert_round_trip::<[i32; 4]>();
+        assert_round_trip::<Location<i64>>();
+        assert_round_trip::<Option<u32>>();
+        assert_round_trip::<(Option<u32>, u32)>();
+    }
+
+    #[test]
+    fn round_trips_words_and_symbols() {
+        assert_round_trip::<char>();
+        assert_round_trip::<bool>();
+        assert_round_trip::<String>();
+        assert_round_trip::<Vec<String>>();
+    }
+
+    #[test]
+    fn round_trips_layouts() {
+        assert_round_trip::<CommaList<u32>>();
+        assert_round_trip::<Lines<Vec<i64>>>();
+        assert_round_trip::<Blocks<Lines<u32>>>();
+        assert_round_trip::<Sections2<Lines<(u32, u32)>, Lines<CommaList<u32>>>>();
+        assert_round_trip::<Sections3<Lines<KeyValue<String, u64>>, CommaList<u8>, Blocks<Lines<char>>>>();
+    }
+
+    #[test]
+    fn parses_layouts_with_any_line_endings() {
+        check(|(blocks, seed): &(Blocks<Lines<u32>>, u64)| {
+            let lines: Vec<String> = blocks
+                .iter()
+                .map(|block| block.iter().map(u32::to_string).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+                .join(&String::new());
+            let input = vary_line_endings(&lines, &mut Rng::new(*seed));
+
+            let result = parse_input(Blocks::<Lines<u32>>::parse)(&input);
+            result == Ok(("", blocks.clone()))
+        });
+    }
+
+    #[test]
+    fn parses_signed_numbers_with_explicit_sign() {
+        check(|number: &i64| {
+            let printed = if *number >= 0 { format!("+{number}") } else { number.to_string() };
+            i64::parse(&printed) == Ok(("", *number))
+        });
+    }
+
+    #[test]
+    fn parses_custom_separators() {
+        check(|(a, b, c): &(u32, u32, u32)| {
+            let input = format!("{a} | {b} | {c}");
+            let result = separated::<(u32, u32, u32)>(" | ")(&input);
+            result == Ok(("", (*a, *b, *c)))
+        });
+
+        check(|values: &[u8; 3]| {
+            let input = values.map(|value| value.to_string()).join("-");
+            let result = separated::<[u8; 3]>("-")(&input);
+            result == Ok(("", *values))
+        });
+    }
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        let result = std::panic::catch_unwind(|| {
+            check_with(1, 256, |numbers: &Vec<u32>| {
+                numbers.iter().all(|n| *n < 100)
+            });
+        });
+
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("counterexample: [100]"), "{message}");
+    }
+
+    #[test]
+    fn shrinks_signed_towards_zero() {
+        assert_eq!((-8i32).shrink(), vec![8, 0, -4, -7]);
+        assert_eq!(1u8.shrink(), vec![0]);
+        assert_eq!(0u8.shrink(), vec![]);
+    }
+
+    #[test]
+    fn parses_lines_with_any_line_endings() {
+        check(|(numbers, seed): &(Vec<u64>, u64)| {
+            let lines: Vec<String> = numbers.iter().map(u64::to_string).collect();
+            let input = vary_line_endings(&lines, &mut Rng::new(*seed));
+
+            let result = parse_input_by_lines(u64::parse)(&input);
+            result == Ok(("", numbers.clone()))
+        });
+    }
+
+    #[test]
+    fn parses_lists_with_any_spacing() {
+        check(|(numbers, spaces): &(Vec<i32>, Vec<u8>)| {
+            let input: String = numbers
+                .iter()
+                .enumerate()
+                .map(|(i, n)| {
+                    let separator = " ".repeat(1 + spaces[i % spaces.len()] as usize % 4);
+                    if i == 0 {
+                        n.to_string()
+                    } else {
+                        format!("{separator}{n}")
+                    }
+                })
+                .collect();
+
+            let result = parse_input(Vec::<i32>::parse)(&input);
+            result == Ok(("", numbers.clone()))
+        });
+    }
+}
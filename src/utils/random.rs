@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+/// A small, seeded pseudo random number generator (SplitMix64).
+/// Not suitable for cryptography, but fast and reproducible across platforms, which is what generated inputs need.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from an empty range"
+        );
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Returns an index into a collection of length `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// Generates a maze of `width`×`height` cells that has exactly one path between any two cells.
+/// Returns a grid of `(2 * width + 1)`×`(2 * height + 1)` tiles where `true` is a wall.
+/// Cells are located at odd coordinates, the border is always a wall.
+pub fn maze(width: usize, height: usize, rng: &mut Rng) -> Vec<Vec<bool>> {
+    let mut walls = vec![vec![true; 2 * width + 1]; 2 * height + 1];
+    let mut visited = vec![vec![false; width]; height];
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+
+    visited[0][0] = true;
+    walls[1][1] = false;
+
+    while let Some(&(x, y)) = stack.last() {
+        let mut neighbours: Vec<(usize, usize)> = [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?)))
+            .filter(|&(nx, ny)| nx < width && ny < height && !visited[ny][nx])
+            .collect();
+
+        if neighbours.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        rng.shuffle(&mut neighbours);
+        let (nx, ny) = neighbours[0];
+
+        visited[ny][nx] = true;
+        walls[2 * ny + 1][2 * nx + 1] = false;
+        walls[y + ny + 1][x + nx + 1] = false;
+        stack.push((nx, ny));
+    }
+
+    walls
+}
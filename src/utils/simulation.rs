@@ -0,0 +1,394 @@
+/// Module that steps through simulations in the terminal, to debug them without sprinkling prints.
+/// Days opt in by implementing [`Simulation`] and registering it with `solution!(DD, simulation = simulate)`, then run
+/// `cargo solve DD --step`.
+use std::io::{stderr, stdin, BufRead, Write};
+use std::process;
+
+use crate::template::{Day, ANSI_BOLD, ANSI_GRAY, ANSI_RESET};
+use crate::utils::terminal::Screen;
+
+/// Every this many steps, the stepper keeps a copy of the state, so that it can go back without keeping all of them.
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// The most steps a single command takes, so that a simulation that never finishes, e.g. a guard walking in a loop, does not
+/// hang the stepper.
+const MAX_STEPS_PER_COMMAND: usize = 1_000_000;
+
+/// A condition the stepper can run until, with the name it is selected by.
+pub type Breakpoint<S> = (&'static str, fn(&S) -> bool);
+
+/// A simulation that advances in discrete steps, like a guard walking or a program executing.
+///
+/// Steps must be deterministic: going back replays them from an earlier copy of the state.
+pub trait Simulation: Clone {
+    /// Advances the simulation by one step. Returns `false` without changing the state if it is finished.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state.
+    fn render(&self) -> Screen;
+
+    /// Describes the current state in a line of text, shown below the screen.
+    fn inspect(&self) -> String {
+        String::new()
+    }
+
+    /// Named conditions the stepper can run until, e.g. `("turn", |guard| guard.turned)`.
+    fn breakpoints() -> Vec<Breakpoint<Self>> {
+        vec![]
+    }
+}
+
+/// Why [`Stepper::run_until`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The predicate holds for the current state.
+    Reached,
+    /// The simulation finished before the predicate held.
+    Finished,
+    /// The predicate did not hold within the given number of steps.
+    Limit,
+}
+
+/// Moves through the steps of a simulation in both directions.
+#[derive(Debug, Clone)]
+pub struct Stepper<S> {
+    state: S,
+    step: usize,
+    /// The states at step 0, `CHECKPOINT_INTERVAL`, `2 * CHECKPOINT_INTERVAL` and so on, as far as the simulation got.
+    checkpoints: Vec<S>,
+    /// The number of steps after which the simulation finishes, once it got there.
+    last: Option<usize>,
+}
+
+impl<S: Simulation> Stepper<S> {
+    pub fn new(simulation: S) -> Self {
+        Self {
+            checkpoints: vec![simulation.clone()],
+            state: simulation,
+            step: 0,
+            last: None,
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// The number of steps taken to get to the current state.
+    pub fn step_count(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.last == Some(self.step)
+    }
+
+    /// Takes up to `steps` steps, fewer if the simulation finishes. Returns the number of steps taken.
+    pub fn forward(&mut self, steps: usize) -> usize {
+        let start = self.step;
+
+        while self.step - start < steps && self.advance() {}
+
+        self.step - start
+    }
+
+    /// Goes back up to `steps` steps, fewer if it reaches the start. Returns the number of steps gone back.
+    pub fn back(&mut self, steps: usize) -> usize {
+        let start = self.step;
+        self.goto(start.saturating_sub(steps));
+
+        start - self.step
+    }
+
+    /// Goes to the state after `step` steps, or to the last state if the simulation finishes earlier.
+    pub fn goto(&mut self, step: usize) {
+        if step < self.step {
+            let checkpoint = (step / CHECKPOINT_INTERVAL).min(self.checkpoints.len() - 1);
+            self.state = self.checkpoints[checkpoint].clone();
+            self.step = checkpoint * CHECKPOINT_INTERVAL;
+        }
+
+        self.forward(step - self.step);
+    }
+
+    /// Steps until `predicate` holds for the state, at least once and at most `max_steps` times.
+    pub fn run_until(&mut self, predicate: impl Fn(&S) -> bool, max_steps: usize) -> Stop {
+        for _ in 0..max_steps {
+            if !self.advance() {
+                return Stop::Finished;
+            }
+
+            if predicate(&self.state) {
+                return Stop::Reached;
+            }
+        }
+
+        Stop::Limit
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.is_finished() || !self.state.step() {
+            self.last = Some(self.step);
+            return false;
+        }
+
+        self.step += 1;
+
+        if self.step == self.checkpoints.len() * CHECKPOINT_INTERVAL {
+            self.checkpoints.push(self.state.clone());
+        }
+
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Forward(usize),
+    Back(usize),
+    Goto(usize),
+    Until(Option<String>),
+    End,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Parses a line typed by the user. An empty line takes a single step.
+    fn parse(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("n");
+        let argument = words.next();
+
+        let count = || argument.map_or(Some(1), |arg| arg.parse().ok());
+
+        let command = match command {
+            "n" | "next" => Command::Forward(count()?),
+            "b" | "back" => Command::Back(count()?),
+            "g" | "goto" => Command::Goto(argument?.parse().ok()?),
+            "u" | "until" => Command::Until(argument.map(String::from)),
+            "e" | "end" => Command::End,
+            "h" | "help" => Command::Help,
+            "q" | "quit" => Command::Quit,
+            _ => return None,
+        };
+
+        words.next().is_none().then_some(command)
+    }
+}
+
+const HELP: &str = "\
+n [N]     take N steps, 1 by default (or just press enter)
+b [N]     go back N steps, 1 by default
+g N       go to step N
+u [NAME]  run until the breakpoint NAME holds, or any breakpoint without a name
+e         run until the simulation finishes
+h         show this help
+q         quit";
+
+/// Runs `simulation` interactively: shows its state on stderr and reads commands from stdin until the user quits.
+pub fn step_through<S: Simulation>(simulation: S) {
+    let mut stepper = Stepper::new(simulation);
+    let breakpoints = S::breakpoints();
+    let mut message = format!("{ANSI_GRAY}h for help{ANSI_RESET}");
+    let limit_reached = format!("no stop within {MAX_STEPS_PER_COMMAND} steps");
+
+    let mut lines = stdin().lock().lines();
+
+    loop {
+        show(&stepper, &message);
+
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+
+        message = String::new();
+
+        match Command::parse(&line) {
+            Some(Command::Forward(steps)) => {
+                if stepper.forward(steps.min(MAX_STEPS_PER_COMMAND)) < steps && !stepper.is_finished() {
+                    message = limit_reached.clone();
+                }
+            }
+            Some(Command::Back(steps)) => {
+                stepper.back(steps);
+            }
+            Some(Command::Goto(step)) => {
+                stepper.goto(step.min(stepper.step_count() + MAX_STEPS_PER_COMMAND));
+                if stepper.step_count() < step && !stepper.is_finished() {
+                    message = limit_reached.clone();
+                }
+            }
+            Some(Command::Until(name)) => {
+                let selected: Vec<_> = breakpoints
+                    .iter()
+                    .filter(|(breakpoint, _)| name.as_deref().is_none_or(|name| name == *breakpoint))
+                    .collect();
+
+                if breakpoints.is_empty() {
+                    message = "this simulation has no breakpoints".to_string();
+                } else if selected.is_empty() {
+                    let names: Vec<_> = breakpoints.iter().map(|(name, _)| *name).collect();
+                    message = format!("unknown breakpoint, available: {}", names.join(", "));
+                } else {
+                    let any_selected = |state: &S| selected.iter().any(|(_, predicate)| predicate(state));
+
+                    match stepper.run_until(any_selected, MAX_STEPS_PER_COMMAND) {
+                        Stop::Reached => {
+                            let names: Vec<_> = selected
+                                .iter()
+                                .filter(|(_, predicate)| predicate(stepper.state()))
+                                .map(|(name, _)| *name)
+                                .collect();
+                            message = format!("stopped at {}", names.join(", "));
+                        }
+                        Stop::Finished => {}
+                        Stop::Limit => message = limit_reached.clone(),
+                    }
+                }
+            }
+            Some(Command::End) => {
+                stepper.forward(MAX_STEPS_PER_COMMAND);
+                if !stepper.is_finished() {
+                    message = limit_reached.clone();
+                }
+            }
+            Some(Command::Help) => message = HELP.to_string(),
+            Some(Command::Quit) => break,
+            None => message = format!("unknown command `{}`, h for help", line.trim()),
+        }
+    }
+}
+
+/// Clears the terminal and shows the state of `stepper`, followed by `message` and a prompt.
+fn show<S: Simulation>(stepper: &Stepper<S>, message: &str) {
+    let mut stderr = stderr().lock();
+    let state = stepper.state();
+    let finished = if stepper.is_finished() { " (finished)" } else { "" };
+
+    let _ = write!(stderr, "\x1b[2J\x1b[H{}", state.render());
+    let _ = writeln!(
+        stderr,
+        "{ANSI_BOLD}step {}{finished}{ANSI_RESET} {}",
+        stepper.step_count(),
+        state.inspect()
+    );
+    if !message.is_empty() {
+        let _ = writeln!(stderr, "{message}");
+    }
+    let _ = write!(stderr, "> ");
+    let _ = stderr.flush();
+}
+
+/// Reports that `cargo solve --step` was used for a day without a simulation and exits.
+pub fn missing_simulation(day: Day) -> ! {
+    eprintln!(
+        "Day {day} does not have a simulation. Register one with `solution!({}, simulation = simulate)`.",
+        day.into_inner()
+    );
+    process::exit(1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::utils::location::Location;
+
+    /// Counts up to a limit.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            self.value += 1;
+            self.value <= self.limit || {
+                self.value -= 1;
+                false
+            }
+        }
+
+        fn render(&self) -> Screen {
+            Screen::from_locations(&[Location::new(self.value as i32, 0)], '#', "")
+        }
+    }
+
+    fn stepper(limit: usize) -> Stepper<Counter> {
+        Stepper::new(Counter { value: 0, limit })
+    }
+
+    #[test]
+    fn steps_forward_until_finished() {
+        let mut stepper = stepper(5);
+
+        assert_eq!(stepper.forward(3), 3);
+        assert_eq!(stepper.state().value, 3);
+        assert!(!stepper.is_finished());
+
+        assert_eq!(stepper.forward(10), 2);
+        assert_eq!(stepper.state().value, 5);
+        assert!(stepper.is_finished());
+        assert_eq!(stepper.forward(1), 0);
+    }
+
+    #[test]
+    fn goes_back_across_checkpoints() {
+        let mut stepper = stepper(1000);
+        stepper.forward(450);
+
+        assert_eq!(stepper.back(200), 200);
+        assert_eq!((stepper.step_count(), stepper.state().value), (250, 250));
+
+        stepper.goto(420);
+        assert_eq!(stepper.state().value, 420);
+
+        assert_eq!(stepper.back(1000), 420);
+        assert_eq!(stepper.state().value, 0);
+
+        stepper.goto(2000);
+        assert_eq!(stepper.step_count(), 1000);
+        assert!(stepper.is_finished());
+    }
+
+    #[test]
+    fn runs_until_predicate() {
+        let mut stepper = stepper(100);
+
+        assert_eq!(stepper.run_until(|counter| counter.value % 30 == 0, 1000), Stop::Reached);
+        assert_eq!(stepper.state().value, 30);
+        assert_eq!(stepper.run_until(|counter| counter.value % 30 == 0, 1000), Stop::Reached);
+        assert_eq!(stepper.state().value, 60);
+
+        assert_eq!(stepper.run_until(|counter| counter.value > 100, 1000), Stop::Finished);
+        assert!(stepper.is_finished());
+    }
+
+    #[test]
+    fn stops_endless_simulations() {
+        let mut stepper = stepper(usize::MAX);
+
+        assert_eq!(stepper.run_until(|_| false, 500), Stop::Limit);
+        assert_eq!(stepper.step_count(), 500);
+
+        assert_eq!(stepper.forward(MAX_STEPS_PER_COMMAND), MAX_STEPS_PER_COMMAND);
+        assert!(!stepper.is_finished());
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(Command::parse(""), Some(Command::Forward(1)));
+        assert_eq!(Command::parse("n 20"), Some(Command::Forward(20)));
+        assert_eq!(Command::parse("back"), Some(Command::Back(1)));
+        assert_eq!(Command::parse("g 7"), Some(Command::Goto(7)));
+        assert_eq!(Command::parse("u"), Some(Command::Until(None)));
+        assert_eq!(Command::parse("u turn"), Some(Command::Until(Some("turn".to_string()))));
+        assert_eq!(Command::parse("g"), None);
+        assert_eq!(Command::parse("n x"), None);
+        assert_eq!(Command::parse("n 1 2"), None);
+        assert_eq!(Command::parse("jump"), None);
+    }
+}
@@ -0,0 +1,240 @@
+/// Module that draws grids and sets of locations in the terminal, e.g. to watch a simulation while debugging it.
+/// Solutions should only draw when [`is_visualizing`](crate::template::runner::is_visualizing), i.e. when started with `--visualize`.
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{stderr, Write};
+use std::thread::sleep;
+use std::time::Duration;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::utils::grid::ByteGrid;
+use crate::utils::location::Location;
+
+/// Maps tiles to the symbol and ANSI color they are drawn with. Tiles drawn as a space are left out.
+#[derive(Debug, Clone)]
+pub struct Glyphs<T> {
+    glyphs: Vec<(T, char, &'static str)>,
+    default: char,
+}
+
+impl<T: PartialEq> Glyphs<T> {
+    pub fn new(default: char) -> Self {
+        Self { glyphs: vec![], default }
+    }
+
+    /// Draws `tile` as `symbol` in `color`, e.g. `with(Tile::Wall, '#', ANSI_GRAY)`. Use `""` for the default color.
+    pub fn with(mut self, tile: T, symbol: char, color: &'static str) -> Self {
+        self.glyphs.push((tile, symbol, color));
+        self
+    }
+
+    pub fn glyph(&self, tile: &T) -> (char, &'static str) {
+        self.find(tile).unwrap_or((self.default, ""))
+    }
+
+    fn find(&self, tile: &T) -> Option<(char, &'static str)> {
+        self.glyphs.iter().find(|(t, _, _)| t == tile).map(|&(_, symbol, color)| (symbol, color))
+    }
+}
+
+/// A frame of colored symbols at arbitrary locations. When displayed, it is cropped to the bounding box of its symbols.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Screen {
+    cells: HashMap<Location<i32>, (char, String)>,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws a grid of tiles, indexed as `grid[y][x]`.
+    pub fn from_grid<T: PartialEq>(grid: &[Vec<T>], glyphs: &Glyphs<T>) -> Self {
+        let mut screen = Self::new();
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let (symbol, color) = glyphs.glyph(tile);
+                screen.set(Location::new(x as i32, y as i32), symbol, color);
+            }
+        }
+
+        screen
+    }
+
+    /// Draws a grid of bytes as they are, except for those with a glyph of their own. The default symbol of `glyphs` is not used.
+    pub fn from_byte_grid(grid: &ByteGrid, glyphs: &Glyphs<u8>) -> Self {
+        let mut screen = Self::new();
+
+        for (loc, byte) in grid.iter() {
+            let (symbol, color) = glyphs.find(&byte).unwrap_or((byte as char, ""));
+            screen.set(loc, symbol, color);
+        }
+
+        screen
+    }
+
+    pub fn from_locations<'a>(locations: impl IntoIterator<Item = &'a Location<i32>>, symbol: char, color: &str) -> Self {
+        let mut screen = Self::new();
+        screen.locations(locations, symbol, color);
+        screen
+    }
+
+    /// Draws `symbol` at `loc`. A space clears the location.
+    pub fn set(&mut self, loc: Location<i32>, symbol: char, color: &str) {
+        if symbol == ' ' {
+            self.cells.remove(&loc);
+        } else {
+            self.cells.insert(loc, (symbol, color.to_string()));
+        }
+    }
+
+    pub fn locations<'a>(&mut self, locations: impl IntoIterator<Item = &'a Location<i32>>, symbol: char, color: &str) {
+        for &loc in locations {
+            self.set(loc, symbol, color);
+        }
+    }
+
+    /// Writes `text` from `loc` to the right, e.g. to label a screen or to show values that are not on a grid.
+    pub fn text(&mut self, loc: Location<i32>, text: &str, color: &str) {
+        for (i, symbol) in text.chars().enumerate() {
+            self.set(loc + Location::new(i as i32, 0), symbol, color);
+        }
+    }
+
+    /// Draws the given locations in bold and `color`, keeping their symbols. Empty locations get a `█`.
+    pub fn highlight<'a>(&mut self, locations: impl IntoIterator<Item = &'a Location<i32>>, color: &str) {
+        for &loc in locations {
+            let symbol = self.cells.get(&loc).map_or('█', |&(symbol, _)| symbol);
+            self.cells.insert(loc, (symbol, format!("{ANSI_BOLD}{color}")));
+        }
+    }
+
+    /// Smallest and largest location with a symbol, if there is any.
+    pub fn bounds(&self) -> Option<(Location<i32>, Location<i32>)> {
+        let min = Location::new(self.cells.keys().map(|loc| loc.x).min()?, self.cells.keys().map(|loc| loc.y).min()?);
+        let max = Location::new(self.cells.keys().map(|loc| loc.x).max()?, self.cells.keys().map(|loc| loc.y).max()?);
+
+        Some((min, max))
+    }
+
+    /// Number of lines the screen takes up when displayed.
+    pub fn height(&self) -> usize {
+        self.bounds().map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+}
+
+impl Display for Screen {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.cells.get(&Location::new(x, y)) {
+                    Some((symbol, color)) if color.is_empty() => write!(f, "{symbol}")?,
+                    Some((symbol, color)) => write!(f, "{color}{symbol}{ANSI_RESET}")?,
+                    None => write!(f, " ")?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Plays screens as an animation on stderr, so that the answers on stdout stay intact.
+/// Every screen replaces the previous one.
+#[derive(Debug)]
+pub struct Player {
+    delay: Duration,
+    previous_height: usize,
+}
+
+impl Player {
+    /// Creates a player that shows `frames_per_second` screens per second.
+    pub fn new(frames_per_second: f64) -> Self {
+        Self {
+            delay: Duration::from_secs_f64(1.0 / frames_per_second),
+            previous_height: 0,
+        }
+    }
+
+    /// Shows `screen` in place of the previous one and waits until it is time for the next one.
+    pub fn show(&mut self, screen: &Screen) {
+        self.show_with_caption(screen, "");
+    }
+
+    /// Like [`Player::show`], with a line of text below the screen, e.g. the step count.
+    pub fn show_with_caption(&mut self, screen: &Screen, caption: &str) {
+        let mut stderr = stderr().lock();
+
+        if self.previous_height > 0 {
+            // Move the cursor back to the first line of the previous screen and clear everything below.
+            let _ = write!(stderr, "\x1b[{}A\x1b[J", self.previous_height);
+        }
+
+        let _ = writeln!(stderr, "{screen}{caption}");
+        let _ = stderr.flush();
+
+        self.previous_height = screen.height() + 1;
+        sleep(self.delay);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::template::ANSI_RED;
+
+    #[test]
+    fn draws_grid_with_glyphs() {
+        let grid = vec![vec![0, 1, 0], vec![0, 2, 0]];
+        let glyphs = Glyphs::new('.').with(1, '#', "").with(2, 'O', ANSI_RED);
+
+        assert_eq!(Screen::from_grid(&grid, &glyphs).to_string(), format!(".#.\n.{ANSI_RED}O{ANSI_RESET}.\n"));
+    }
+
+    #[test]
+    fn crops_to_bounding_box() {
+        let locations = [Location::new(10, 5), Location::new(12, 6), Location::new(11, 7)];
+        let screen = Screen::from_locations(&locations, '#', "");
+
+        assert_eq!(screen.to_string(), "#  \n  #\n # \n");
+        assert_eq!(screen.height(), 3);
+        assert_eq!(Screen::new().to_string(), "");
+    }
+
+    #[test]
+    fn leaves_out_blank_tiles() {
+        let grid = ByteGrid::new("...\n.#.\n...\n");
+        let screen = Screen::from_byte_grid(&grid, &Glyphs::new('?').with(b'.', ' ', ""));
+
+        assert_eq!(screen.to_string(), "#\n");
+    }
+
+    #[test]
+    fn writes_text() {
+        let mut screen = Screen::new();
+        screen.text(Location::new(0, 0), "A: 7", "");
+        screen.text(Location::new(0, 1), "B", "");
+
+        assert_eq!(screen.to_string(), "A: 7\nB   \n");
+    }
+
+    #[test]
+    fn highlights_locations() {
+        let mut screen = Screen::from_byte_grid(&ByteGrid::new("ab\n"), &Glyphs::new('?'));
+        screen.highlight(&[Location::new(1, 0), Location::new(2, 0)], ANSI_RED);
+
+        assert_eq!(
+            screen.to_string(),
+            format!("a{ANSI_BOLD}{ANSI_RED}b{ANSI_RESET}{ANSI_BOLD}{ANSI_RED}█{ANSI_RESET}\n")
+        );
+    }
+}
@@ -0,0 +1,249 @@
+/// Module that renders grids and sets of locations to images, e.g. to look at the state of a simulation.
+/// Images are written as PNG, several frames can be assembled into an animated GIF.
+/// Days write their images from a [`Render`](crate::template::runner::Render) function, into the directory the runner passes in.
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, ImageResult, Rgb, RgbImage};
+
+use crate::utils::grid::ByteGrid;
+use crate::utils::location::Location;
+
+pub type Color = Rgb<u8>;
+
+pub const BLACK: Color = Rgb([0, 0, 0]);
+pub const WHITE: Color = Rgb([255, 255, 255]);
+pub const GRAY: Color = Rgb([96, 96, 96]);
+pub const RED: Color = Rgb([220, 50, 47]);
+pub const GREEN: Color = Rgb([80, 200, 80]);
+pub const BLUE: Color = Rgb([38, 139, 210]);
+pub const YELLOW: Color = Rgb([255, 200, 0]);
+
+/// Maps tiles to colors. Tiles without a color of their own get the default color.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    colors: Vec<(T, Color)>,
+    default: Color,
+}
+
+impl<T: PartialEq> Palette<T> {
+    pub fn new(default: Color) -> Self {
+        Self { colors: vec![], default }
+    }
+
+    pub fn with(mut self, tile: T, color: Color) -> Self {
+        self.colors.push((tile, color));
+        self
+    }
+
+    pub fn color(&self, tile: &T) -> Color {
+        self.colors.iter().find(|(t, _)| t == tile).map_or(self.default, |&(_, color)| color)
+    }
+}
+
+/// An image of a grid, where every tile is a square of `scale`×`scale` pixels.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    image: RgbImage,
+    scale: u32,
+}
+
+impl Canvas {
+    pub fn new(size: Location<i32>, scale: u32, background: Color) -> Self {
+        let image = RgbImage::from_pixel(size.x.max(0) as u32 * scale, size.y.max(0) as u32 * scale, background);
+        Self { image, scale }
+    }
+
+    /// Renders a grid of tiles, indexed as `grid[y][x]`.
+    pub fn from_grid<T: PartialEq>(grid: &[Vec<T>], palette: &Palette<T>, scale: u32) -> Self {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut canvas = Self::new(Location::new(width as i32, grid.len() as i32), scale, palette.default);
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                canvas.fill(Location::new(x as i32, y as i32), palette.color(tile));
+            }
+        }
+
+        canvas
+    }
+
+    pub fn from_byte_grid(grid: &ByteGrid, palette: &Palette<u8>, scale: u32) -> Self {
+        let mut canvas = Self::new(grid.size(), scale, palette.default);
+
+        for (loc, byte) in grid.iter() {
+            canvas.fill(loc, palette.color(&byte));
+        }
+
+        canvas
+    }
+
+    /// Renders a set of locations on a canvas of the given size. Locations outside of it are skipped.
+    pub fn from_locations<'a>(locations: impl IntoIterator<Item = &'a Location<i32>>, size: Location<i32>, color: Color, scale: u32) -> Self {
+        let mut canvas = Self::new(size, scale, BLACK);
+        canvas.locations(locations, color);
+        canvas
+    }
+
+    /// Colors the tile at `loc`, does nothing if it is outside of the canvas.
+    pub fn fill(&mut self, loc: Location<i32>, color: Color) {
+        self.rect(loc.map(|v| v * self.scale as i32), self.scale, color);
+    }
+
+    pub fn locations<'a>(&mut self, locations: impl IntoIterator<Item = &'a Location<i32>>, color: Color) {
+        for &loc in locations {
+            self.fill(loc, color);
+        }
+    }
+
+    /// Draws a line through the centers of the tiles of `path`, on top of the tiles.
+    /// Consecutive locations do not need to be adjacent.
+    pub fn path(&mut self, path: &[Location<i32>], color: Color) {
+        let scale = self.scale as i32;
+        let width = (self.scale / 3).max(1);
+        let center = |loc: Location<i32>| loc * scale + Location::new(scale / 2, scale / 2) - Location::new(width as i32 / 2, width as i32 / 2);
+
+        for (&from, &to) in path.iter().zip(path.iter().skip(1)) {
+            let (from, to) = (center(from), center(to));
+            let delta = to - from;
+            let steps = delta.x.abs().max(delta.y.abs()).max(1);
+
+            for step in 0..=steps {
+                let x = from.x + delta.x * step / steps;
+                let y = from.y + delta.y * step / steps;
+                self.rect(Location::new(x, y), width, color);
+            }
+        }
+
+        if let [single] = path {
+            self.rect(center(*single), width, color);
+        }
+    }
+
+    pub fn image(&self) -> &RgbImage {
+        &self.image
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        self.image.save_with_format(path, image::ImageFormat::Png)
+    }
+
+    /// Fills a square of `size` pixels with its top left corner at `corner`, clipped to the image.
+    fn rect(&mut self, corner: Location<i32>, size: u32, color: Color) {
+        let (width, height) = self.image.dimensions();
+
+        for y in corner.y.max(0)..(corner.y + size as i32).min(height as i32) {
+            for x in corner.x.max(0)..(corner.x + size as i32).min(width as i32) {
+                self.image.put_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+}
+
+/// Frames of an animation that can be saved as a looping GIF.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<RgbImage>,
+    delay_ms: u32,
+}
+
+impl Animation {
+    /// Creates an empty animation that shows each frame for `delay_ms` milliseconds.
+    pub fn new(delay_ms: u32) -> Self {
+        Self { frames: vec![], delay_ms }
+    }
+
+    pub fn push(&mut self, canvas: &Canvas) {
+        self.frames.push(canvas.image.clone());
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let delay = Delay::from_numer_denom_ms(self.delay_ms, 1);
+        let frames = self
+            .frames
+            .iter()
+            .map(|image| Frame::from_parts(DynamicImage::ImageRgb8(image.clone()).into_rgba8(), 0, 0, delay));
+
+        encoder.encode_frames(frames)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_grid_with_palette() {
+        let grid = vec![vec!['#', '.'], vec!['.', 'S']];
+        let palette = Palette::new(BLACK).with('#', GRAY).with('S', GREEN);
+        let canvas = Canvas::from_grid(&grid, &palette, 2);
+
+        assert_eq!(canvas.image().dimensions(), (4, 4));
+        assert_eq!(canvas.image().get_pixel(1, 1), &GRAY);
+        assert_eq!(canvas.image().get_pixel(2, 0), &BLACK);
+        assert_eq!(canvas.image().get_pixel(3, 3), &GREEN);
+    }
+
+    #[test]
+    fn renders_byte_grids_and_locations() {
+        let grid = ByteGrid::new("#.\n.#\n");
+        let canvas = Canvas::from_byte_grid(&grid, &Palette::new(BLACK).with(b'#', WHITE), 1);
+
+        let locations = [Location::new(0, 0), Location::new(1, 1), Location::new(5, 5)];
+        let expected = Canvas::from_locations(&locations, Location::new(2, 2), WHITE, 1);
+
+        assert_eq!(canvas.image(), expected.image());
+    }
+
+    #[test]
+    fn overlays_paths() {
+        let mut canvas = Canvas::new(Location::new(3, 3), 3, BLACK);
+        canvas.path(&[Location::new(0, 0), Location::new(2, 0), Location::new(2, 2)], RED);
+
+        let lit: Vec<_> = canvas
+            .image()
+            .enumerate_pixels()
+            .filter(|(_, _, &pixel)| pixel == RED)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+
+        assert!(lit.contains(&(1, 1)) && lit.contains(&(4, 1)) && lit.contains(&(7, 1)) && lit.contains(&(7, 7)));
+        assert!(!lit.contains(&(1, 7)));
+    }
+
+    #[test]
+    fn writes_png_and_gif() {
+        let dir = std::env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut canvas = Canvas::new(Location::new(2, 2), 4, BLACK);
+        let mut animation = Animation::new(100);
+        animation.push(&canvas);
+        canvas.fill(Location::new(1, 1), YELLOW);
+        animation.push(&canvas);
+
+        canvas.save_png(dir.join("frame.png")).unwrap();
+        animation.save_gif(dir.join("animation.gif")).unwrap();
+
+        assert_eq!(image::open(dir.join("frame.png")).unwrap().to_rgb8(), *canvas.image());
+        assert_eq!(animation.len(), 2);
+        assert!(std::fs::metadata(dir.join("animation.gif")).unwrap().len() > 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}